@@ -0,0 +1,131 @@
+//! Generates code from the solutions in `src/bin`:
+//! the aggregate `solutions` binary and the tests of example cases for every solution.
+use std::{
+    collections::BTreeMap,
+    env, fs,
+    path::{Path, PathBuf},
+};
+
+#[allow(dead_code)]
+#[path = "src/template/examples/cases.rs"]
+mod cases;
+
+/// Solution binaries are named like `2023-01.rs`.
+fn is_solution(stem: &str) -> bool {
+    stem.len() == 7
+        && stem
+            .char_indices()
+            .all(|(i, c)| if i == 4 { c == '-' } else { c.is_ascii_digit() })
+}
+
+fn main() {
+    println!("cargo:rerun-if-changed=src/bin");
+
+    let manifest_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").unwrap());
+    let out_dir = PathBuf::from(env::var("OUT_DIR").unwrap());
+    let bin_dir = manifest_dir.join("src/bin");
+
+    let mut stems: Vec<String> = fs::read_dir(&bin_dir)
+        .unwrap()
+        .filter_map(|entry| {
+            let name = entry.ok()?.file_name().into_string().ok()?;
+            let stem = name.strip_suffix(".rs")?;
+            is_solution(stem).then(|| stem.to_string())
+        })
+        .collect();
+    stems.sort_unstable();
+
+    write_solutions(&bin_dir, &stems, &out_dir);
+    write_example_tests(&manifest_dir, &stems, &out_dir);
+}
+
+fn write_solutions(bin_dir: &Path, stems: &[String], out_dir: &Path) {
+    let modules: Vec<String> = stems
+        .iter()
+        .map(|stem| format!("_{}", stem.replace('-', "_")))
+        .collect();
+
+    let mut out = String::new();
+
+    // solutions define a global allocator for dhat, which can only exist once per binary.
+    // their tests already run in the solution binaries and include files generated per binary,
+    // so the modules are left out of test builds of this binary, e.g. with `--all-targets`.
+    let cfg = "#[cfg(not(any(test, feature = \"dhat-heap\")))]";
+
+    for (stem, module) in stems.iter().zip(&modules) {
+        out += &format!(
+            "{cfg}\n#[allow(dead_code)]\n#[path = {:?}]\nmod {module};\n",
+            bin_dir.join(format!("{stem}.rs"))
+        );
+    }
+
+    out += &format!(
+        "\n{cfg}\nconst SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[\n"
+    );
+    for module in &modules {
+        out += &format!("    {module}::SOLUTION,\n");
+    }
+    out += "];\n\n#[cfg(any(test, feature = \"dhat-heap\"))]\nconst SOLUTIONS: &[advent_of_code::template::registry::Solution] = &[];\n";
+
+    fs::write(out_dir.join("solutions.rs"), out).unwrap();
+}
+
+/// Turns a case name like `Simple loop #2` into an identifier like `simple_loop_2`.
+fn to_identifier(name: &str) -> String {
+    let mut ident = String::new();
+    for c in name.chars() {
+        if c.is_ascii_alphanumeric() {
+            ident.push(c.to_ascii_lowercase());
+        } else if !ident.is_empty() && !ident.ends_with('_') {
+            ident.push('_');
+        }
+    }
+    ident.trim_end_matches('_').to_string()
+}
+
+/// Writes `example_tests/<year>-<day>.rs` for every solution, to be included by `example_tests!`.
+/// Solutions without a cases file get an empty file.
+fn write_example_tests(manifest_dir: &Path, stems: &[String], out_dir: &Path) {
+    let tests_dir = out_dir.join("example_tests");
+    fs::create_dir_all(&tests_dir).unwrap();
+
+    for stem in stems {
+        let (year, day) = stem.split_once('-').unwrap();
+        let examples_dir = manifest_dir.join("data").join(year).join("examples");
+        // cargo would re-run the script on every build for a path that does not exist.
+        if examples_dir.exists() {
+            println!("cargo:rerun-if-changed={}", examples_dir.display());
+        }
+
+        let path = examples_dir.join(format!("{day}.cases"));
+        let contents = fs::read_to_string(&path).unwrap_or_default();
+        let cases = cases::parse_cases(&contents)
+            .unwrap_or_else(|e| panic!("invalid cases file {}: {e}", path.display()));
+
+        // names that only differ in punctuation or case would generate the same test twice.
+        let mut names: BTreeMap<String, &str> = BTreeMap::new();
+        let mut out = String::new();
+
+        for case in &cases {
+            let ident = to_identifier(&case.name);
+            if let Some(other) = names.insert(ident.clone(), &case.name) {
+                panic!(
+                    "cases `{other}` and `{}` in {} both generate tests named `example_{ident}_*`, rename one of them.",
+                    case.name,
+                    path.display()
+                );
+            }
+
+            for (part, func) in [(1, "part_one"), (2, "part_two")] {
+                if case.expected(part).is_some() {
+                    out += &format!(
+                        "#[test]\nfn example_{ident}_{func}() {{\n    advent_of_code::template::examples::__check_case({func}, PUZZLE, {:?}, {part});\n}}\n\n",
+                        case.name
+                    );
+                }
+            }
+        }
+
+        fs::write(tests_dir.join(format!("{stem}.rs")), out).unwrap();
+    }
+}
@@ -1,20 +1,16 @@
-use std::{
-    collections::HashMap,
-    fmt::{Display, Formatter},
-};
+use std::fmt::{Display, Formatter};
 
+use advent_of_code::{
+    cycle::find_cycle,
+    geometry::Direction,
+    template::visualize::{self, Cell, Color, Frame},
+};
 use itertools::Itertools;
 
 advent_of_code::solution!(14);
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 struct Platform(Vec<Vec<char>>);
-enum Direction {
-    North,
-    South,
-    West,
-    East,
-}
 
 impl Display for Platform {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
@@ -63,6 +59,14 @@ impl Platform {
         row
     }
 
+    /// The platform with rounded rocks highlighted.
+    fn frame(&self) -> Frame {
+        Frame::from_fn(self.0[0].len(), self.0.len(), |x, y| match self.0[y][x] {
+            'O' => Cell::new('O').color(Color::Yellow),
+            c => Cell::new(c).color(Color::Gray),
+        })
+    }
+
     fn score(&self) -> u32 {
         self.0
             .iter()
@@ -73,23 +77,23 @@ impl Platform {
     }
 
     fn cycle(&self) -> Self {
-        self.tilt(Direction::North)
-            .tilt(Direction::West)
-            .tilt(Direction::South)
-            .tilt(Direction::East)
+        self.tilt(Direction::Up)
+            .tilt(Direction::Left)
+            .tilt(Direction::Down)
+            .tilt(Direction::Right)
     }
 
     fn tilt(&self, direction: Direction) -> Self {
         match direction {
-            Direction::North => self
+            Direction::Up => self
                 .transpose()
                 .reverse_rows()
-                .tilt(Direction::East)
+                .tilt(Direction::Right)
                 .reverse_rows()
                 .transpose(),
-            Direction::South => self.transpose().tilt(Direction::East).transpose(),
-            Direction::West => self.reverse_rows().tilt(Direction::East).reverse_rows(),
-            Direction::East => Platform(
+            Direction::Down => self.transpose().tilt(Direction::Right).transpose(),
+            Direction::Left => self.reverse_rows().tilt(Direction::Right).reverse_rows(),
+            Direction::Right => Platform(
                 self.0
                     .iter()
                     .map(|row| {
@@ -132,33 +136,22 @@ impl Platform {
 
 pub fn part_one(input: &str) -> Option<u32> {
     let mut platform = Platform::parse(input);
-    platform = platform.tilt(Direction::North);
+    visualize::frame(|| platform.frame().with_caption("before tilting"));
+    platform = platform.tilt(Direction::Up);
+    visualize::frame(|| platform.frame().with_caption("tilted north"));
     Some(platform.score())
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut platform = Platform::parse(input);
-    let mut cycles = HashMap::from([(platform.clone(), 0)]);
-    let mut cur_cycle = 1;
-    loop {
-        platform = platform.cycle();
-        if cycles.keys().contains(&platform) {
-            break;
-        }
-        cycles.insert(platform.clone(), cur_cycle);
-        cur_cycle += 1;
-    }
-    let cycle_start = cycles[&platform];
-
-    let cycle_length = cur_cycle - cycles[&platform];
-    let platform_cycle = cycles
-        .into_iter()
-        .filter(|(_, i)| (cycle_start..cur_cycle).contains(i))
-        .sorted_by(|(_, i), (_, j)| i.cmp(j))
-        .map(|(v, _)| v)
-        .collect_vec();
-
-    Some(platform_cycle[(1_000_000_000 - cycle_start) % cycle_length].score())
+    let mut cycles = 0;
+    let history = find_cycle(Platform::parse(input), |platform| {
+        let platform = platform.cycle();
+        cycles += 1;
+        visualize::frame(|| platform.frame().with_caption(format!("cycle {cycles}")));
+        platform
+    });
+
+    Some(history.state_at(1_000_000_000).score())
 }
 
 #[cfg(test)]
@@ -167,13 +160,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(136));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(64));
     }
 }
@@ -1,5 +1,9 @@
 use std::collections::HashSet;
 
+use advent_of_code::{
+    geometry::{Direction, Point},
+    template::visualize::{self, Cell, Color, Frame},
+};
 use itertools::Itertools;
 
 advent_of_code::solution!(16);
@@ -20,49 +24,20 @@ enum GridItem {
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 struct LightBeam {
-    row: usize,
-    col: usize,
+    position: Point,
     direction: Direction,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
-enum Direction {
-    Up,
-    Down,
-    Left,
-    Right,
-}
-
-impl Direction {
-    fn turn_left(&self) -> Direction {
-        match self {
-            Direction::Up => Direction::Left,
-            Direction::Left => Direction::Down,
-            Direction::Down => Direction::Right,
-            Direction::Right => Direction::Up,
-        }
-    }
-    fn turn_right(&self) -> Direction {
-        match self {
-            Direction::Up => Direction::Right,
-            Direction::Right => Direction::Down,
-            Direction::Down => Direction::Left,
-            Direction::Left => Direction::Up,
-        }
-    }
-}
-
 impl LightBeam {
     fn set_direction(&self, direction: &Direction) -> Self {
         Self {
-            row: self.row,
-            col: self.col,
+            position: self.position,
             direction: *direction,
         }
     }
 
     fn step_in(&self, grid: &LightGrid) -> Vec<Self> {
-        let dirs = match (grid.map[self.row][self.col], self.direction) {
+        let dirs = match (grid.at(self.position), self.direction) {
             (GridItem::HorizontalPipe, Direction::Up | Direction::Down) => {
                 vec![Direction::Left, Direction::Right]
             }
@@ -85,31 +60,15 @@ impl LightBeam {
             .collect()
     }
 
-    fn move_in(&self, drow: i32, dcol: i32, grid: &LightGrid) -> Option<Self> {
-        if (self.row == 0 && drow < 0)
-            || (self.row == grid.map.len() - 1 && drow > 0)
-            || (self.col == 0 && dcol < 0)
-            || (self.col == grid.map[0].len() - 1 && dcol > 0)
-        {
-            return None;
-        }
-
-        Some(Self {
-            row: (self.row as i32 + drow) as usize,
-            col: (self.col as i32 + dcol) as usize,
+    fn move_one_in(&self, grid: &LightGrid) -> Option<Self> {
+        let position = self.position + self.direction;
+        grid.contains(position).then_some(Self {
+            position,
             direction: self.direction,
         })
     }
-
-    fn move_one_in(&self, grid: &LightGrid) -> Option<Self> {
-        match self.direction {
-            Direction::Up => self.move_in(-1, 0, grid),
-            Direction::Down => self.move_in(1, 0, grid),
-            Direction::Left => self.move_in(0, -1, grid),
-            Direction::Right => self.move_in(0, 1, grid),
-        }
-    }
 }
+
 impl LightGrid {
     fn parse(input: &str) -> Self {
         let mut grid = Vec::new();
@@ -130,10 +89,19 @@ impl LightGrid {
         Self { map: grid }
     }
 
+    fn contains(&self, p: Point) -> bool {
+        p.to_usize()
+            .is_some_and(|(x, y)| y < self.map.len() && x < self.map[y].len())
+    }
+
+    fn at(&self, p: Point) -> GridItem {
+        let (x, y) = p.to_usize().unwrap();
+        self.map[y][x]
+    }
+
     fn launch_beam(&self, row: usize, col: usize, direction: Direction) -> usize {
         let beam = LightBeam {
-            row,
-            col,
+            position: Point::from((col, row)),
             direction,
         };
 
@@ -141,6 +109,7 @@ impl LightGrid {
         let mut active_beams = vec![beam];
 
         while !active_beams.is_empty() {
+            visualize::frame(|| self.frame(&beam_path, &active_beams));
             active_beams = active_beams
                 .iter()
                 .flat_map(|b| b.step_in(self))
@@ -148,13 +117,41 @@ impl LightGrid {
                 .collect::<Vec<_>>();
             beam_path.extend(active_beams.iter());
         }
-        let energized_tiles = beam_path
-            .into_iter()
-            .sorted_by(|a, b| a.row.cmp(&b.row).then(a.col.cmp(&b.col)))
-            .dedup_by(|a, b| a.row == b.row && a.col == b.col)
-            .collect::<Vec<_>>();
+        visualize::frame(|| self.frame(&beam_path, &active_beams));
+        beam_path.iter().map(|b| b.position).unique().count()
+    }
+
+    /// The grid with energized tiles highlighted and the heads of the active beams.
+    fn frame(&self, beam_path: &HashSet<LightBeam>, active_beams: &[LightBeam]) -> Frame {
+        let mut frame = Frame::from_fn(self.map[0].len(), self.map.len(), |col, row| {
+            let symbol = match self.map[row][col] {
+                GridItem::HorizontalPipe => '-',
+                GridItem::VerticalPipe => '|',
+                GridItem::RightAngledMirror => '/',
+                GridItem::LeftAngledMirror => '\\',
+                GridItem::Nothing => '.',
+            };
+            Cell::new(symbol).color(Color::Gray)
+        });
+
+        for beam in beam_path {
+            let (col, row) = beam.position.to_usize().unwrap();
+            frame.update(col, row, |cell| cell.color(Color::Yellow).highlight());
+        }
+
+        for beam in active_beams {
+            let arrow = match beam.direction {
+                Direction::Up => '^',
+                Direction::Down => 'v',
+                Direction::Left => '<',
+                Direction::Right => '>',
+            };
+            let (col, row) = beam.position.to_usize().unwrap();
+            frame.set(col, row, Cell::new(arrow).color(Color::Red));
+        }
 
-        energized_tiles.len()
+        let energized = beam_path.iter().map(|b| b.position).unique().count();
+        frame.with_caption(format!("{energized} tiles energized"))
     }
 }
 
@@ -164,6 +161,8 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
+    // every beam would be animated, part one already shows how they spread.
+    let _paused = visualize::pause();
     let grid = LightGrid::parse(input);
     let maximum_energized_tiles = (0..grid.map.len())
         .flat_map(|row| {
@@ -190,13 +189,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(46));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, None);
     }
 }
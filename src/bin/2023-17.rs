@@ -0,0 +1,78 @@
+use advent_of_code::{
+    geometry::{Direction, Point},
+    grid::Grid,
+    search::dijkstra,
+};
+
+advent_of_code::solution!(17);
+
+/// Position, direction and steps taken in that direction.
+type CrucibleState = (Point, Direction, usize);
+
+struct Map(Grid<u32>);
+
+impl Map {
+    fn parse(input: &str) -> Self {
+        Map(Grid::parse(input, |c| c.to_digit(10)).unwrap())
+    }
+
+    fn successors(
+        &self,
+        (position, direction, steps): CrucibleState,
+        min_forward_steps: usize,
+        max_forward_steps: usize,
+    ) -> impl Iterator<Item = (CrucibleState, u32)> + '_ {
+        let forward = (steps < max_forward_steps).then_some((direction, steps + 1));
+        let turns = (steps >= min_forward_steps)
+            .then_some([(direction.turn_left(), 1), (direction.turn_right(), 1)])
+            .into_iter()
+            .flatten();
+
+        forward
+            .into_iter()
+            .chain(turns)
+            .filter_map(move |(direction, steps)| {
+                let next = self.0.step(position, direction)?;
+                Some(((next, direction, steps), self.0[next]))
+            })
+    }
+
+    fn lowest_heat_loss(&self, min_forward_steps: usize, max_forward_steps: usize) -> Option<u32> {
+        let goal = Point::new(self.0.width() as isize - 1, self.0.height() as isize - 1);
+        let starts = [Direction::Down, Direction::Right].map(|d| (Point::ORIGIN, d, 1));
+
+        let path = dijkstra(
+            starts,
+            |state| self.successors(*state, min_forward_steps, max_forward_steps),
+            |(position, _, _)| *position == goal,
+        )?;
+        Some(path.cost)
+    }
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    let map = Map::parse(input);
+    map.lowest_heat_loss(0, 3)
+}
+
+pub fn part_two(input: &str) -> Option<u32> {
+    let map = Map::parse(input);
+    map.lowest_heat_loss(4, 10)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(102));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(94));
+    }
+}
@@ -0,0 +1,190 @@
+/// Cycle detection for simulations that run for too many steps, e.g. "what is the state after a billion steps?".
+///
+/// A deterministic simulation with finitely many states eventually repeats a state. From then on it runs in a
+/// cycle, so the state after any number of steps is the state after one of the first steps.
+use std::{
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+};
+
+/// A simulation that repeats the states from step `prefix_length` onwards every `length` steps.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cycle {
+    pub prefix_length: usize,
+    pub length: usize,
+}
+
+impl Cycle {
+    /// The earliest step with the same state as step `n`.
+    pub fn reduce(&self, n: usize) -> usize {
+        if n < self.prefix_length {
+            n
+        } else {
+            self.prefix_length + (n - self.prefix_length) % self.length
+        }
+    }
+
+    /// Runs the simulation from the start to find the state after `n` steps, but at most until the end of
+    /// the first cycle.
+    pub fn state_at<S>(&self, initial: S, mut step: impl FnMut(&S) -> S, n: usize) -> S {
+        (0..self.reduce(n)).fold(initial, |state, _| step(&state))
+    }
+}
+
+/// The states of a simulation up to the first repeated state.
+#[derive(Clone, Debug)]
+pub struct History<S> {
+    pub cycle: Cycle,
+    states: Vec<S>,
+}
+
+impl<S> History<S> {
+    /// The state after `n` steps.
+    pub fn state_at(&self, n: usize) -> &S {
+        &self.states[self.cycle.reduce(n)]
+    }
+
+    /// The states before the cycle, followed by one pass through the cycle.
+    pub fn states(&self) -> &[S] {
+        &self.states
+    }
+}
+
+/// Finds the cycle by storing every state in a hash map. Use [`find_cycle_by_key`] if states are expensive
+/// to hash or [`brent`] if they do not fit into memory.
+pub fn find_cycle<S: Clone + Eq + Hash>(initial: S, step: impl FnMut(&S) -> S) -> History<S> {
+    find_cycle_by_key(initial, step, S::clone)
+}
+
+/// Like [`find_cycle`], but compares states by `key`, e.g. a hash of a large state or only the part of a
+/// state that determines the next steps.
+pub fn find_cycle_by_key<S, K: Eq + Hash>(
+    initial: S,
+    mut step: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+) -> History<S> {
+    let mut steps = HashMap::from([(key(&initial), 0)]);
+    let mut states = vec![initial];
+
+    loop {
+        let next = step(&states[states.len() - 1]);
+
+        match steps.entry(key(&next)) {
+            Entry::Occupied(entry) => {
+                let prefix_length = *entry.get();
+                let cycle = Cycle {
+                    prefix_length,
+                    length: states.len() - prefix_length,
+                };
+                return History { cycle, states };
+            }
+            Entry::Vacant(entry) => {
+                entry.insert(states.len());
+                states.push(next);
+            }
+        }
+    }
+}
+
+/// Finds the cycle with Brent's algorithm, which only keeps two states in memory at a time but runs the
+/// simulation up to three times as long as [`find_cycle`]. Use [`Cycle::state_at`] to get a state afterwards.
+pub fn brent<S: Clone + Eq>(initial: &S, step: impl FnMut(&S) -> S) -> Cycle {
+    brent_by_key(initial, step, S::clone)
+}
+
+/// Like [`brent`], but compares states by `key`.
+pub fn brent_by_key<S: Clone, K: Eq>(
+    initial: &S,
+    mut step: impl FnMut(&S) -> S,
+    mut key: impl FnMut(&S) -> K,
+) -> Cycle {
+    // find the length of the cycle: the hare runs ahead and the tortoise teleports to it at every power of two.
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = key(initial);
+    let mut hare = step(initial);
+    let mut hare_key = key(&hare);
+
+    while hare_key != tortoise {
+        if power == length {
+            tortoise = hare_key;
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        hare_key = key(&hare);
+        length += 1;
+    }
+
+    // with the hare `length` steps ahead, both meet at the start of the cycle.
+    let mut tortoise = initial.clone();
+    let mut hare = (0..length).fold(initial.clone(), |state, _| step(&state));
+    let mut prefix_length = 0;
+
+    while key(&tortoise) != key(&hare) {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix_length += 1;
+    }
+
+    Cycle {
+        prefix_length,
+        length,
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, brent_by_key, find_cycle, find_cycle_by_key, Cycle};
+
+    /// 0, 1, 2, 3, 4, 5, 6, 2, 3, ...
+    fn step(n: &u32) -> u32 {
+        if *n == 6 {
+            2
+        } else {
+            n + 1
+        }
+    }
+
+    #[test]
+    fn finds_cycles() {
+        let expected = Cycle {
+            prefix_length: 2,
+            length: 5,
+        };
+
+        let history = find_cycle(0, step);
+        assert_eq!(history.cycle, expected);
+        assert_eq!(history.states(), &[0, 1, 2, 3, 4, 5, 6]);
+        assert_eq!(*history.state_at(1), 1);
+        assert_eq!(*history.state_at(1_000_000_000), 5);
+
+        assert_eq!(brent(&0, step), expected);
+        assert_eq!(expected.state_at(0, step, 1_000_000_000), 5);
+    }
+
+    #[test]
+    fn finds_cycles_by_key() {
+        // only the counter determines the next state, the number of steps taken is ignored.
+        let step = |(n, steps): &(u32, usize)| (step(n), steps + 1);
+
+        let history = find_cycle_by_key((0, 0), step, |(n, _)| *n);
+        assert_eq!(history.cycle.length, 5);
+        assert_eq!(history.state_at(12), &(2, 2));
+
+        assert_eq!(brent_by_key(&(0, 0), step, |(n, _)| *n), history.cycle);
+    }
+
+    #[test]
+    fn finds_fixed_points() {
+        let cycle = brent(&3, |_| 7);
+        assert_eq!(
+            cycle,
+            Cycle {
+                prefix_length: 1,
+                length: 1
+            }
+        );
+        assert_eq!(find_cycle(7, |_| 7).cycle.prefix_length, 0);
+    }
+}
@@ -0,0 +1,469 @@
+/// Points, vectors and directions for movement on a two-dimensional grid.
+///
+/// Coordinates follow [`Grid`](crate::grid::Grid): `x` grows to the right and `y` grows downwards,
+/// so [`Direction::Up`] decreases `y`.
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign},
+    str::FromStr,
+};
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Point {
+    pub x: isize,
+    pub y: isize,
+}
+
+/// The difference between two points, e.g. a step in some direction.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vector {
+    pub x: isize,
+    pub y: isize,
+}
+
+impl Point {
+    pub const ORIGIN: Point = Point { x: 0, y: 0 };
+
+    pub const fn new(x: isize, y: isize) -> Self {
+        Point { x, y }
+    }
+
+    /// Adds a vector, returning `None` on overflow.
+    pub fn checked_add(self, v: Vector) -> Option<Self> {
+        Some(Point::new(
+            self.x.checked_add(v.x)?,
+            self.y.checked_add(v.y)?,
+        ))
+    }
+
+    /// Subtracts a vector, returning `None` on overflow.
+    pub fn checked_sub(self, v: Vector) -> Option<Self> {
+        Some(Point::new(
+            self.x.checked_sub(v.x)?,
+            self.y.checked_sub(v.y)?,
+        ))
+    }
+
+    /// Adds a vector, wrapping around at the bounds of `isize`.
+    #[must_use]
+    pub fn wrapping_add(self, v: Vector) -> Self {
+        Point::new(self.x.wrapping_add(v.x), self.y.wrapping_add(v.y))
+    }
+
+    /// Subtracts a vector, wrapping around at the bounds of `isize`.
+    #[must_use]
+    pub fn wrapping_sub(self, v: Vector) -> Self {
+        Point::new(self.x.wrapping_sub(v.x), self.y.wrapping_sub(v.y))
+    }
+
+    /// Maps the point into a `width` by `height` area that repeats infinitely in all directions.
+    /// Panics if `width` or `height` is zero.
+    #[must_use]
+    pub fn wrap(self, width: usize, height: usize) -> Self {
+        Point::new(
+            self.x.rem_euclid(width as isize),
+            self.y.rem_euclid(height as isize),
+        )
+    }
+
+    pub fn manhattan_distance(self, other: Point) -> usize {
+        (other - self).manhattan_length()
+    }
+
+    /// The orthogonal neighbors, in the order of [`Direction::ALL`].
+    pub fn neighbors4(self) -> [Point; 4] {
+        Direction::ALL.map(|d| self + d)
+    }
+
+    /// The orthogonal and diagonal neighbors, in the order of [`Direction8::ALL`].
+    pub fn neighbors8(self) -> [Point; 8] {
+        Direction8::ALL.map(|d| self + d.vector())
+    }
+
+    /// The coordinates as indices, or `None` if either is negative.
+    pub fn to_usize(self) -> Option<(usize, usize)> {
+        Some((usize::try_from(self.x).ok()?, usize::try_from(self.y).ok()?))
+    }
+}
+
+impl Vector {
+    pub const fn new(x: isize, y: isize) -> Self {
+        Vector { x, y }
+    }
+
+    pub fn manhattan_length(self) -> usize {
+        self.x.unsigned_abs() + self.y.unsigned_abs()
+    }
+}
+
+impl From<(usize, usize)> for Point {
+    fn from((x, y): (usize, usize)) -> Self {
+        Point::new(x as isize, y as isize)
+    }
+}
+
+impl From<(isize, isize)> for Point {
+    fn from((x, y): (isize, isize)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl Display for Point {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({}, {})", self.x, self.y)
+    }
+}
+
+impl Add<Vector> for Point {
+    type Output = Point;
+
+    fn add(self, v: Vector) -> Self::Output {
+        Point::new(self.x + v.x, self.y + v.y)
+    }
+}
+
+impl Add<Direction> for Point {
+    type Output = Point;
+
+    fn add(self, d: Direction) -> Self::Output {
+        self + d.vector()
+    }
+}
+
+impl AddAssign<Vector> for Point {
+    fn add_assign(&mut self, v: Vector) {
+        *self = *self + v;
+    }
+}
+
+impl AddAssign<Direction> for Point {
+    fn add_assign(&mut self, d: Direction) {
+        *self = *self + d;
+    }
+}
+
+impl Sub<Vector> for Point {
+    type Output = Point;
+
+    fn sub(self, v: Vector) -> Self::Output {
+        Point::new(self.x - v.x, self.y - v.y)
+    }
+}
+
+impl SubAssign<Vector> for Point {
+    fn sub_assign(&mut self, v: Vector) {
+        *self = *self - v;
+    }
+}
+
+impl Sub for Point {
+    type Output = Vector;
+
+    fn sub(self, other: Point) -> Self::Output {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Add for Vector {
+    type Output = Vector;
+
+    fn add(self, other: Vector) -> Self::Output {
+        Vector::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl Sub for Vector {
+    type Output = Vector;
+
+    fn sub(self, other: Vector) -> Self::Output {
+        Vector::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl Neg for Vector {
+    type Output = Vector;
+
+    fn neg(self) -> Self::Output {
+        Vector::new(-self.x, -self.y)
+    }
+}
+
+impl Mul<isize> for Vector {
+    type Output = Vector;
+
+    fn mul(self, factor: isize) -> Self::Output {
+        Vector::new(self.x * factor, self.y * factor)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
+
+impl Direction {
+    /// All directions, clockwise starting at the top.
+    pub const ALL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction::Up => Vector::new(0, -1),
+            Direction::Right => Vector::new(1, 0),
+            Direction::Down => Vector::new(0, 1),
+            Direction::Left => Vector::new(-1, 0),
+        }
+    }
+
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        match self {
+            Direction::Up => Direction::Left,
+            Direction::Left => Direction::Down,
+            Direction::Down => Direction::Right,
+            Direction::Right => Direction::Up,
+        }
+    }
+
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        match self {
+            Direction::Up => Direction::Down,
+            Direction::Right => Direction::Left,
+            Direction::Down => Direction::Up,
+            Direction::Left => Direction::Right,
+        }
+    }
+
+    pub fn is_horizontal(self) -> bool {
+        matches!(self, Direction::Left | Direction::Right)
+    }
+
+    pub fn is_vertical(self) -> bool {
+        !self.is_horizontal()
+    }
+}
+
+/// Directions including diagonals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Direction8 {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction8 {
+    /// All directions, clockwise starting at the top.
+    pub const ALL: [Direction8; 8] = [
+        Direction8::Up,
+        Direction8::UpRight,
+        Direction8::Right,
+        Direction8::DownRight,
+        Direction8::Down,
+        Direction8::DownLeft,
+        Direction8::Left,
+        Direction8::UpLeft,
+    ];
+
+    fn index(self) -> usize {
+        Direction8::ALL.iter().position(|d| *d == self).unwrap()
+    }
+
+    pub fn vector(self) -> Vector {
+        match self {
+            Direction8::Up => Vector::new(0, -1),
+            Direction8::UpRight => Vector::new(1, -1),
+            Direction8::Right => Vector::new(1, 0),
+            Direction8::DownRight => Vector::new(1, 1),
+            Direction8::Down => Vector::new(0, 1),
+            Direction8::DownLeft => Vector::new(-1, 1),
+            Direction8::Left => Vector::new(-1, 0),
+            Direction8::UpLeft => Vector::new(-1, -1),
+        }
+    }
+
+    /// Turns by 45 degrees counterclockwise.
+    #[must_use]
+    pub fn turn_left(self) -> Self {
+        Direction8::ALL[(self.index() + 7) % 8]
+    }
+
+    /// Turns by 45 degrees clockwise.
+    #[must_use]
+    pub fn turn_right(self) -> Self {
+        Direction8::ALL[(self.index() + 1) % 8]
+    }
+
+    #[must_use]
+    pub fn reverse(self) -> Self {
+        Direction8::ALL[(self.index() + 4) % 8]
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        self.index() % 2 == 1
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(d: Direction) -> Self {
+        match d {
+            Direction::Up => Direction8::Up,
+            Direction::Right => Direction8::Right,
+            Direction::Down => Direction8::Down,
+            Direction::Left => Direction8::Left,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct ParseDirectionError(String);
+
+impl Error for ParseDirectionError {}
+
+impl Display for ParseDirectionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a direction", self.0)
+    }
+}
+
+/// Parses `U`/`R`/`D`/`L`, `N`/`E`/`S`/`W` and arrows like `^`/`>`/`v`/`<`.
+impl TryFrom<char> for Direction {
+    type Error = ParseDirectionError;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            'U' | 'N' | '^' | '↑' => Ok(Direction::Up),
+            'R' | 'E' | '>' | '→' => Ok(Direction::Right),
+            'D' | 'S' | 'v' | '↓' => Ok(Direction::Down),
+            'L' | 'W' | '<' | '←' => Ok(Direction::Left),
+            _ => Err(ParseDirectionError(c.to_string())),
+        }
+    }
+}
+
+/// Parses the same chars as `TryFrom<char>`, surrounding whitespace is ignored.
+impl FromStr for Direction {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut chars = s.trim().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Direction::try_from(c),
+            _ => Err(ParseDirectionError(s.to_string())),
+        }
+    }
+}
+
+/// Parses a single direction like `Direction` or two of `N`/`S` and `E`/`W` (or `U`/`D` and `L`/`R`) for diagonals, e.g. `NE`.
+impl FromStr for Direction8 {
+    type Err = ParseDirectionError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let error = || ParseDirectionError(s.to_string());
+        let s = s.trim();
+
+        let mut directions = s.chars().map(Direction::try_from);
+        let first = directions.next().ok_or_else(error)?.map_err(|_| error())?;
+
+        let Some(second) = directions.next() else {
+            return Ok(first.into());
+        };
+        let second = second.map_err(|_| error())?;
+
+        if directions.next().is_some() {
+            return Err(error());
+        }
+
+        match (first, second) {
+            (Direction::Up, Direction::Right) => Ok(Direction8::UpRight),
+            (Direction::Down, Direction::Right) => Ok(Direction8::DownRight),
+            (Direction::Down, Direction::Left) => Ok(Direction8::DownLeft),
+            (Direction::Up, Direction::Left) => Ok(Direction8::UpLeft),
+            _ => Err(error()),
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::str::FromStr;
+
+    use super::{Direction, Direction8, Point, Vector};
+
+    #[test]
+    fn moves_points() {
+        let p = Point::new(2, 3);
+        assert_eq!(p + Direction::Up, Point::new(2, 2));
+        assert_eq!(p + Direction::Left.vector() * 3, Point::new(-1, 3));
+        assert_eq!(Point::new(5, 1) - p, Vector::new(3, -2));
+        assert_eq!(p.manhattan_distance(Point::new(-1, 5)), 5);
+        assert_eq!(p.checked_add(Vector::new(isize::MAX, 0)), None);
+        assert_eq!(
+            Point::new(isize::MAX, 0).wrapping_add(Vector::new(1, 0)),
+            Point::new(isize::MIN, 0)
+        );
+        assert_eq!(
+            Point::new(0, isize::MIN).wrapping_sub(Vector::new(0, 1)),
+            Point::new(0, isize::MAX)
+        );
+        assert_eq!(Point::new(-1, 7).wrap(5, 5), Point::new(4, 2));
+        assert_eq!(Point::new(-1, 0).to_usize(), None);
+        assert_eq!(Point::from((1_usize, 2_usize)).to_usize(), Some((1, 2)));
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.reverse(), Direction::Right);
+        assert_eq!(Direction8::Up.turn_right(), Direction8::UpRight);
+        assert_eq!(Direction8::Up.turn_left(), Direction8::UpLeft);
+        assert_eq!(Direction8::UpRight.reverse(), Direction8::DownLeft);
+        assert!(Direction8::DownLeft.is_diagonal());
+
+        for d in Direction::ALL {
+            assert_eq!(d.turn_left().turn_left(), d.reverse());
+            assert_eq!(Direction8::from(d).vector(), d.vector());
+        }
+    }
+
+    #[test]
+    fn parses_directions() {
+        assert_eq!(Direction::try_from('U'), Ok(Direction::Up));
+        assert_eq!(Direction::try_from('E'), Ok(Direction::Right));
+        assert_eq!(Direction::try_from('v'), Ok(Direction::Down));
+        assert_eq!(Direction::from_str(" L "), Ok(Direction::Left));
+        assert!(Direction::from_str("X").is_err());
+        assert!(Direction::from_str("UL").is_err());
+
+        assert_eq!(Direction8::from_str("NE"), Ok(Direction8::UpRight));
+        assert_eq!(Direction8::from_str("DL"), Ok(Direction8::DownLeft));
+        assert_eq!(Direction8::from_str("W"), Ok(Direction8::Left));
+        assert!(Direction8::from_str("NS").is_err());
+    }
+}
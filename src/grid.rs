@@ -0,0 +1,434 @@
+/// A two-dimensional grid, the shape of most puzzle inputs.
+///
+/// Cells are stored row by row in a single `Vec` and addressed by their column `x` and row `y`,
+/// with `(0, 0)` in the top left corner. Cells can also be addressed by a [`Point`], which
+/// saves casts when moving around.
+use std::{
+    error::Error,
+    fmt::{self, Display},
+    ops::{Index, IndexMut},
+};
+
+use crate::geometry::{Direction, Direction8, Point, Vector};
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGridError {
+    /// A line is longer or shorter than the first one.
+    Ragged { y: usize },
+    /// The closure passed to [`Grid::parse`] did not accept a char.
+    InvalidChar { c: char, x: usize, y: usize },
+}
+
+impl Error for ParseGridError {}
+
+impl Display for ParseGridError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseGridError::Ragged { y } => {
+                write!(f, "line {} differs in length from the first line", y + 1)
+            }
+            ParseGridError::InvalidChar { c, x, y } => {
+                write!(f, "unexpected `{c}` at column {}, line {}", x + 1, y + 1)
+            }
+        }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of `width` columns and `height` rows, all set to `value`.
+    pub fn new(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![value; width * height],
+        }
+    }
+
+    /// Creates a grid with the value of every column `x` and row `y`.
+    pub fn from_fn(width: usize, height: usize, mut f: impl FnMut(usize, usize) -> T) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| f(x, y))
+            .collect();
+
+        Grid {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Parses lines of text, mapping every char to a cell. `f` returns `None` for chars that are not allowed.
+    ///
+    /// ```ignore
+    /// let chars = Grid::parse(input, Some)?;
+    /// let digits = Grid::parse(input, |c| c.to_digit(10))?;
+    /// ```
+    pub fn parse(
+        input: &str,
+        mut f: impl FnMut(char) -> Option<T>,
+    ) -> Result<Self, ParseGridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (y, line) in input.lines().enumerate() {
+            let len_before = cells.len();
+
+            for (x, c) in line.chars().enumerate() {
+                cells.push(f(c).ok_or(ParseGridError::InvalidChar { c, x, y })?);
+            }
+
+            let line_width = cells.len() - len_before;
+            if *width.get_or_insert(line_width) != line_width {
+                return Err(ParseGridError::Ragged { y });
+            }
+            height += 1;
+        }
+
+        Ok(Grid {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn in_bounds(&self, x: isize, y: isize) -> bool {
+        (0..self.width as isize).contains(&x) && (0..self.height as isize).contains(&y)
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        (x < self.width && y < self.height).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    /// Gets a cell of the grid repeated infinitely in all directions, e.g. `(-1, 0)` is the last cell of the first row.
+    /// Returns `None` if the grid is empty.
+    pub fn get_wrapping(&self, x: isize, y: isize) -> Option<&T> {
+        if self.cells.is_empty() {
+            return None;
+        }
+        Some(&self[Point::new(x, y).wrap(self.width, self.height)])
+    }
+
+    pub fn contains(&self, p: Point) -> bool {
+        self.in_bounds(p.x, p.y)
+    }
+
+    pub fn at(&self, p: Point) -> Option<&T> {
+        let (x, y) = p.to_usize()?;
+        self.get(x, y)
+    }
+
+    pub fn at_mut(&mut self, p: Point) -> Option<&mut T> {
+        let (x, y) = p.to_usize()?;
+        self.get_mut(x, y)
+    }
+
+    /// Like [`Grid::get_wrapping`], for points.
+    pub fn at_wrapping(&self, p: Point) -> Option<&T> {
+        self.get_wrapping(p.x, p.y)
+    }
+
+    /// The point next to `p` in direction `d`, or `None` if it lies outside of the grid.
+    pub fn step(&self, p: Point, d: Direction) -> Option<Point> {
+        let next = p.checked_add(d.vector())?;
+        self.contains(next).then_some(next)
+    }
+
+    /// Orthogonal neighbors of a cell that lie within the grid.
+    pub fn neighbors4(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(x, y, Direction::ALL.map(Direction::vector))
+    }
+
+    /// Orthogonal and diagonal neighbors of a cell that lie within the grid.
+    pub fn neighbors8(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.neighbors(x, y, Direction8::ALL.map(Direction8::vector))
+    }
+
+    fn neighbors<const N: usize>(
+        &self,
+        x: usize,
+        y: usize,
+        offsets: [Vector; N],
+    ) -> impl Iterator<Item = (usize, usize)> + '_ {
+        let p = Point::from((x, y));
+        offsets.into_iter().filter_map(move |v| {
+            p.checked_add(v)
+                .filter(|next| self.contains(*next))?
+                .to_usize()
+        })
+    }
+
+    /// All points of the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |i| Point::from((i % width, i / width)))
+    }
+
+    pub fn row(&self, y: usize) -> &[T] {
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics for a size of zero.
+        self.cells.chunks(self.width.max(1))
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} is out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// All cells with their position, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        let width = self.width;
+        self.cells
+            .iter()
+            .enumerate()
+            .map(move |(i, value)| ((i % width, i / width), value))
+    }
+
+    /// The position of the first cell, row by row, that matches `predicate`.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<(usize, usize)> {
+        self.iter()
+            .find_map(|(pos, value)| predicate(value).then_some(pos))
+    }
+
+    /// The positions of all cells that match `predicate`.
+    pub fn positions<'a>(
+        &'a self,
+        mut predicate: impl FnMut(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = (usize, usize)> + 'a {
+        self.iter()
+            .filter_map(move |(pos, value)| predicate(value).then_some(pos))
+    }
+
+    /// The position of the first cell, row by row, that equals `value`.
+    pub fn find(&self, value: &T) -> Option<(usize, usize)>
+    where
+        T: PartialEq,
+    {
+        self.position(|x| x == value)
+    }
+
+    pub fn map<U>(&self, mut f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(&mut f).collect(),
+        }
+    }
+
+    /// Mirrors the grid along its main diagonal, rows become columns.
+    #[must_use]
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |x, y| self[(y, x)].clone())
+    }
+
+    /// Rotates the grid by 90 degrees clockwise, the first column becomes the first row in reverse.
+    #[must_use]
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |x, y| {
+            self[(y, self.height - 1 - x)].clone()
+        })
+    }
+
+    /// Rotates the grid by 90 degrees counterclockwise, the last column becomes the first row.
+    #[must_use]
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Grid::from_fn(self.height, self.width, |x, y| {
+            self[(self.width - 1 - y, x)].clone()
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        self.get(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        self.get_mut(x, y)
+            .unwrap_or_else(|| panic!("({x}, {y}) is out of bounds"))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, p: Point) -> &Self::Output {
+        self.at(p).unwrap_or_else(|| panic!("{p} is out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, p: Point) -> &mut Self::Output {
+        self.at_mut(p)
+            .unwrap_or_else(|| panic!("{p} is out of bounds"))
+    }
+}
+
+/// Writes the cells of each row next to each other and the rows on separate lines.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (y, row) in self.rows().enumerate() {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for value in row {
+                write!(f, "{value}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, ParseGridError};
+    use crate::geometry::{Direction, Point};
+
+    fn get_mock_grid() -> Grid<char> {
+        Grid::parse("abc\ndef", Some).unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = get_mock_grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(2, 1)], 'f');
+        assert_eq!(grid.get(3, 0), None);
+        assert_eq!(grid.to_string(), "abc\ndef");
+
+        let digits = Grid::parse("12\n34\n", |c| c.to_digit(10)).unwrap();
+        assert_eq!(digits.row(1), &[3, 4]);
+
+        assert_eq!(
+            Grid::parse("12\n3x", |c| c.to_digit(10)),
+            Err(ParseGridError::InvalidChar { c: 'x', x: 1, y: 1 })
+        );
+        assert_eq!(
+            Grid::parse("ab\nc", Some),
+            Err(ParseGridError::Ragged { y: 1 })
+        );
+    }
+
+    #[test]
+    fn finds_neighbors() {
+        let grid = get_mock_grid();
+        assert_eq!(
+            grid.neighbors4(0, 0).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(
+            grid.neighbors8(1, 0).collect::<Vec<_>>(),
+            vec![(2, 0), (2, 1), (1, 1), (0, 1), (0, 0)]
+        );
+    }
+
+    #[test]
+    fn addresses_points() {
+        let mut grid = get_mock_grid();
+        let start = Point::from(grid.find(&'a').unwrap());
+        assert_eq!(grid.step(start, Direction::Up), None);
+        assert_eq!(grid.step(start, Direction::Right), Some(Point::new(1, 0)));
+        assert_eq!(grid.at(Point::new(-1, 0)), None);
+        assert_eq!(grid.at_wrapping(Point::new(-1, 0)), Some(&'c'));
+
+        grid[Point::new(2, 1)] = 'x';
+        assert_eq!(grid.to_string(), "abc\ndex");
+        assert_eq!(grid.points().last(), Some(Point::new(2, 1)));
+    }
+
+    #[test]
+    fn wraps_around() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.get_wrapping(-1, 0), Some(&'c'));
+        assert_eq!(grid.get_wrapping(4, -3), Some(&'e'));
+        assert!(!grid.in_bounds(-1, 0));
+        assert!(grid.in_bounds(2, 1));
+    }
+
+    #[test]
+    fn does_not_wrap_empty_grids() {
+        let grid = Grid::parse("", Some).unwrap();
+        assert_eq!(grid.get_wrapping(-1, 0), None);
+        assert_eq!(grid.at_wrapping(Point::new(3, 3)), None);
+    }
+
+    #[test]
+    fn views_rows_and_columns() {
+        let grid = get_mock_grid();
+        assert_eq!(
+            grid.rows().collect::<Vec<_>>(),
+            vec![&['a', 'b', 'c'], &['d', 'e', 'f']]
+        );
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.rotate_clockwise().rotate_counterclockwise(), grid);
+    }
+
+    #[test]
+    fn finds_cells() {
+        let grid = get_mock_grid();
+        assert_eq!(grid.find(&'e'), Some((1, 1)));
+        assert_eq!(grid.find(&'z'), None);
+        assert_eq!(
+            grid.positions(|c| "aeiou".contains(*c)).collect::<Vec<_>>(),
+            vec![(0, 0), (1, 1)]
+        );
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).to_string(), "ABC\nDEF");
+    }
+}
@@ -1,77 +1,215 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, examples, leaderboard, read, readme, scaffold, solve, time, verify, watch,
+};
 use args::{parse, AppArguments};
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
+use advent_of_code::template::PuzzleId;
 #[cfg(feature = "today")]
 use std::process;
 
 mod args {
-    use advent_of_code::template::Day;
+    use advent_of_code::template::commands::time;
+    use advent_of_code::template::limits::{parse_bytes, parse_duration};
+    use advent_of_code::template::{visualize, Day, Limits, PuzzleId, Tolerance, Year};
     use std::process;
 
     pub enum AppArguments {
         Download {
-            day: Day,
+            puzzle: PuzzleId,
         },
         Read {
-            day: Day,
+            puzzle: PuzzleId,
+        },
+        Examples {
+            puzzle: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            puzzle: PuzzleId,
             download: bool,
+            examples: bool,
+            template: Option<String>,
+            answer_type: Option<String>,
         },
         Solve {
-            day: Day,
+            puzzle: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            input: Option<String>,
+            visualize: Option<visualize::Settings>,
         },
         All {
+            year: Year,
             release: bool,
+            jobs: usize,
+            limits: Limits,
         },
         Time {
-            all: bool,
+            year: Year,
+            day: Option<Day>,
+            options: time::Options,
+        },
+        Verify {
+            year: Year,
+        },
+        Readme {
+            year: Year,
+        },
+        Leaderboard {
+            year: Year,
+            id: Option<String>,
+            file: Option<String>,
             day: Option<Day>,
-            store: bool,
+        },
+        Watch {
+            puzzle: PuzzleId,
+            release: bool,
         },
         #[cfg(feature = "today")]
         Today,
     }
 
+    /// Reads the `--year` option, falling back to the `AOC_YEAR` environment variable.
+    fn parse_year(args: &mut pico_args::Arguments) -> Result<Year, Box<dyn std::error::Error>> {
+        args.opt_value_from_str("--year")?
+            .or_else(Year::from_env)
+            .ok_or_else(|| "no year specified. Pass `--year` or set `AOC_YEAR`.".into())
+    }
+
+    /// Reads the `--timeout`, `--part-timeout` and `--memory-limit` options.
+    fn parse_limits(args: &mut pico_args::Arguments) -> Result<Limits, pico_args::Error> {
+        Ok(Limits {
+            part_timeout: args.opt_value_from_fn("--part-timeout", parse_duration)?,
+            day_timeout: args.opt_value_from_fn("--timeout", parse_duration)?,
+            memory_limit: args.opt_value_from_fn("--memory-limit", parse_bytes)?,
+        })
+    }
+
+    /// Reads the `--visualize`, `--fps` and `--visualize-file` options.
+    fn parse_visualize(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Option<visualize::Settings>, pico_args::Error> {
+        let enabled = args.contains("--visualize");
+        let fps = args.opt_value_from_str("--fps")?;
+        let file = args.opt_value_from_str("--visualize-file")?;
+
+        if !enabled && file.is_none() {
+            return Ok(None);
+        }
+
+        let defaults = visualize::Settings::default();
+        Ok(Some(visualize::Settings {
+            fps: fps.unwrap_or(defaults.fps),
+            file,
+        }))
+    }
+
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
+                year: parse_year(&mut args)?,
                 release: args.contains("--release"),
+                jobs: args.opt_value_from_str("--jobs")?.unwrap_or(1),
+                limits: parse_limits(&mut args)?,
             },
             Some("time") => {
-                let all = args.contains("--all");
+                let year = parse_year(&mut args)?;
+                let run_all = args.contains("--all");
                 let store = args.contains("--store");
+                let memory = args.contains("--memory");
+                let compare = args.opt_value_from_str("--compare")?;
+                let tolerance: Option<Tolerance> = args.opt_value_from_str("--tolerance")?;
+                let check = args
+                    .contains("--check")
+                    .then(|| tolerance.unwrap_or_default());
+                let limits = parse_limits(&mut args)?;
+                let input = args.opt_value_from_str("--input")?;
 
                 AppArguments::Time {
-                    all,
+                    year,
                     day: args.opt_free_from_str()?,
-                    store,
+                    options: time::Options {
+                        run_all,
+                        store,
+                        compare,
+                        check,
+                        memory,
+                        limits,
+                        input,
+                    },
+                }
+            }
+            Some("verify") => AppArguments::Verify {
+                year: parse_year(&mut args)?,
+            },
+            Some("readme") => AppArguments::Readme {
+                year: parse_year(&mut args)?,
+            },
+            Some("leaderboard") => {
+                let year = parse_year(&mut args)?;
+                let file = args.opt_value_from_str("--file")?;
+                let day = args.opt_value_from_str("--day")?;
+
+                AppArguments::Leaderboard {
+                    year,
+                    id: args.opt_free_from_str()?,
+                    file,
+                    day,
+                }
+            }
+            Some("watch") => {
+                let year = parse_year(&mut args)?;
+                let release = args.contains("--release");
+
+                AppArguments::Watch {
+                    puzzle: PuzzleId::new(year, args.free_from_str()?),
+                    release,
                 }
             }
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                puzzle: PuzzleId::new(parse_year(&mut args)?, args.free_from_str()?),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
-            },
-            Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
-                download: args.contains("--download"),
+                puzzle: PuzzleId::new(parse_year(&mut args)?, args.free_from_str()?),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
+            Some("examples") => AppArguments::Examples {
+                puzzle: PuzzleId::new(parse_year(&mut args)?, args.free_from_str()?),
             },
+            Some("scaffold") => {
+                let year = parse_year(&mut args)?;
+                let download = args.contains("--download");
+                let examples = args.contains("--examples");
+                let template = args.opt_value_from_str("--template")?;
+                let answer_type = args.opt_value_from_str("--answer-type")?;
+
+                AppArguments::Scaffold {
+                    puzzle: PuzzleId::new(year, args.free_from_str()?),
+                    download,
+                    examples,
+                    template,
+                    answer_type,
+                }
+            }
+            Some("solve") => {
+                let year = parse_year(&mut args)?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let input = args.opt_value_from_str("--input")?;
+                let visualize = parse_visualize(&mut args)?;
+
+                AppArguments::Solve {
+                    puzzle: PuzzleId::new(year, args.free_from_str()?),
+                    release,
+                    dhat,
+                    submit,
+                    input,
+                    visualize,
+                }
+            }
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
             Some(x) => {
@@ -100,29 +238,56 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
+            AppArguments::All {
+                year,
+                release,
+                jobs,
+                limits,
+            } => all::handle(year, release, jobs, limits),
+            AppArguments::Time { year, day, options } => time::handle(year, day, options),
+            AppArguments::Verify { year } => verify::handle(year),
+            AppArguments::Readme { year } => readme::handle(year),
+            AppArguments::Leaderboard {
+                year,
+                id,
+                file,
+                day,
+            } => leaderboard::handle(year, id, file, day),
+            AppArguments::Watch { puzzle, release } => watch::handle(puzzle, release),
+            AppArguments::Download { puzzle } => download::handle(puzzle),
+            AppArguments::Read { puzzle } => read::handle(puzzle),
+            AppArguments::Examples { puzzle } => examples::handle(puzzle),
+            AppArguments::Scaffold {
+                puzzle,
+                download,
+                examples,
+                template,
+                answer_type,
+            } => {
+                // download first, so the template can use the puzzle title.
                 if download {
-                    download::handle(day);
+                    download::handle(puzzle);
+                }
+                scaffold::handle(puzzle, template.as_deref(), answer_type.as_deref());
+                if examples {
+                    examples::handle(puzzle);
                 }
             }
             AppArguments::Solve {
-                day,
+                puzzle,
                 release,
                 dhat,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                input,
+                visualize,
+            } => solve::handle(puzzle, release, dhat, submit, input, visualize),
             #[cfg(feature = "today")]
             AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
+                match PuzzleId::today() {
+                    Some(puzzle) => {
+                        download::handle(puzzle);
+                        scaffold::handle(puzzle, None, None);
+                        read::handle(puzzle)
                     }
                     None => {
                         eprintln!(
@@ -0,0 +1,303 @@
+/// Searches on implicit graphs, which are described by their start states and a closure that yields the
+/// successors of a state. States are only created when they are reached, so there is no need to build a graph
+/// of all states up front.
+///
+/// All searches accept several start states, which all start at a cost of zero.
+use std::{
+    cmp::Reverse,
+    collections::{hash_map::Entry, BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+    ops::Add,
+};
+
+/// A path from one of the start states to a goal.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Path<N, C> {
+    /// The states of the path, starting with the start state and ending with the goal.
+    pub states: Vec<N>,
+    pub cost: C,
+}
+
+impl<N, C> Path<N, C> {
+    pub fn start(&self) -> &N {
+        &self.states[0]
+    }
+
+    pub fn goal(&self) -> &N {
+        &self.states[self.states.len() - 1]
+    }
+}
+
+/// A reached state with the index of the state it was reached from.
+struct Visit<N, C> {
+    state: N,
+    parent: Option<usize>,
+    cost: C,
+}
+
+fn reconstruct_path<N: Clone, C: Copy>(visits: &[Visit<N, C>], goal: usize) -> Path<N, C> {
+    let mut states = vec![];
+    let mut index = Some(goal);
+
+    while let Some(i) = index {
+        states.push(visits[i].state.clone());
+        index = visits[i].parent;
+    }
+
+    states.reverse();
+    Path {
+        states,
+        cost: visits[goal].cost,
+    }
+}
+
+/// Finds a path with the fewest steps to a state that satisfies `is_goal`.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut visits: Vec<Visit<N, usize>> = vec![];
+    let mut seen: HashSet<N> = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if seen.insert(start.clone()) {
+            queue.push_back(visits.len());
+            visits.push(Visit {
+                state: start,
+                parent: None,
+                cost: 0,
+            });
+        }
+    }
+
+    while let Some(index) = queue.pop_front() {
+        if is_goal(&visits[index].state) {
+            return Some(reconstruct_path(&visits, index));
+        }
+
+        let cost = visits[index].cost + 1;
+        for next in successors(&visits[index].state) {
+            if seen.insert(next.clone()) {
+                queue.push_back(visits.len());
+                visits.push(Visit {
+                    state: next,
+                    parent: Some(index),
+                    cost,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// Finds the fewest steps to every state that can be reached in at most `max_steps` steps, or at all if `None`.
+pub fn bfs_distances<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    max_steps: Option<usize>,
+) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut distances: HashMap<N, usize> = HashMap::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if !distances.contains_key(&start) {
+            distances.insert(start.clone(), 0);
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, steps)) = queue.pop_front() {
+        if max_steps.is_some_and(|max_steps| steps >= max_steps) {
+            continue;
+        }
+
+        for next in successors(&state) {
+            if let Entry::Vacant(entry) = distances.entry(next) {
+                queue.push_back((entry.key().clone(), steps + 1));
+                entry.insert(steps + 1);
+            }
+        }
+    }
+
+    distances
+}
+
+/// Finds a path with the lowest cost to a state that satisfies `is_goal`.
+/// `successors` yields the next states together with the cost of moving there, which must not be negative.
+pub fn dijkstra<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    successors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(starts, successors, |_| C::default(), is_goal)
+}
+
+/// Like [`dijkstra`], but explores states in the order of their cost plus the estimated cost to a goal.
+/// The path is guaranteed to have the lowest cost if `heuristic` never overestimates the remaining cost,
+/// e.g. the manhattan distance to the goal if every step costs at least `1`.
+pub fn astar<N, C, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut successors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (N, C)>,
+{
+    // states are stored once and referred to by index, so they do not need to be `Ord` to be put on the heap.
+    let mut visits: Vec<Visit<N, C>> = vec![];
+    let mut indices: HashMap<N, usize> = HashMap::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        if let Entry::Vacant(entry) = indices.entry(start) {
+            let index = visits.len();
+            heap.push(Reverse((heuristic(entry.key()), C::default(), index)));
+            visits.push(Visit {
+                state: entry.key().clone(),
+                parent: None,
+                cost: C::default(),
+            });
+            entry.insert(index);
+        }
+    }
+
+    while let Some(Reverse((_, cost, index))) = heap.pop() {
+        // a cheaper way to this state was found after this entry was pushed.
+        if cost > visits[index].cost {
+            continue;
+        }
+
+        if is_goal(&visits[index].state) {
+            return Some(reconstruct_path(&visits, index));
+        }
+
+        for (next, step_cost) in successors(&visits[index].state) {
+            let next_cost = cost + step_cost;
+
+            match indices.entry(next) {
+                Entry::Vacant(entry) => {
+                    let next_index = visits.len();
+                    heap.push(Reverse((
+                        next_cost + heuristic(entry.key()),
+                        next_cost,
+                        next_index,
+                    )));
+                    visits.push(Visit {
+                        state: entry.key().clone(),
+                        parent: Some(index),
+                        cost: next_cost,
+                    });
+                    entry.insert(next_index);
+                }
+                Entry::Occupied(entry) => {
+                    let next_index = *entry.get();
+                    if next_cost < visits[next_index].cost {
+                        let visit = &mut visits[next_index];
+                        visit.parent = Some(index);
+                        visit.cost = next_cost;
+                        heap.push(Reverse((
+                            next_cost + heuristic(&visit.state),
+                            next_cost,
+                            next_index,
+                        )));
+                    }
+                }
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, bfs_distances, dijkstra};
+    use crate::{
+        geometry::{Direction, Point},
+        grid::Grid,
+    };
+
+    fn get_mock_grid() -> Grid<char> {
+        Grid::parse("S.#.\n..#G\n....", Some).unwrap()
+    }
+
+    fn walk(grid: &Grid<char>, p: Point) -> impl Iterator<Item = Point> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |d| grid.step(p, d))
+            .filter(|next| grid[*next] != '#')
+    }
+
+    #[test]
+    fn finds_shortest_paths() {
+        let grid = get_mock_grid();
+        let start = Point::from(grid.find(&'S').unwrap());
+        let goal = Point::from(grid.find(&'G').unwrap());
+
+        let path = bfs([start], |p| walk(&grid, *p), |p| *p == goal).unwrap();
+        assert_eq!(path.cost, 6);
+        assert_eq!(path.states.len(), 7);
+        assert_eq!((*path.start(), *path.goal()), (start, goal));
+
+        assert!(bfs([start], |p| walk(&grid, *p), |p| grid[*p] == 'X').is_none());
+    }
+
+    #[test]
+    fn finds_distances() {
+        let grid = get_mock_grid();
+        let start = Point::from(grid.find(&'S').unwrap());
+
+        let distances = bfs_distances([start], |p| walk(&grid, *p), None);
+        assert_eq!(distances.len(), 10);
+        assert_eq!(distances[&Point::new(3, 0)], 7);
+
+        let distances = bfs_distances([start], |p| walk(&grid, *p), Some(2));
+        assert_eq!(distances.len(), 5);
+    }
+
+    #[test]
+    fn finds_cheapest_paths() {
+        // moving down is expensive, so the cheapest path takes a detour.
+        let cost = |from: &Point, to: &Point| if to.y > from.y { 10 } else { 1 };
+        let successors = |p: &Point| {
+            let p = *p;
+            [Point::new(p.x + 1, p.y), Point::new(p.x, p.y + 1)]
+                .into_iter()
+                .filter(|next| next.x <= 3 && next.y <= 1)
+                .map(move |next| (next, cost(&p, &next)))
+        };
+        let goal = Point::new(3, 1);
+
+        let path = dijkstra([Point::ORIGIN], successors, |p| *p == goal).unwrap();
+        assert_eq!(path.cost, 13);
+
+        let path = astar(
+            [Point::ORIGIN, Point::new(3, 0)],
+            successors,
+            |p| p.manhattan_distance(goal),
+            |p| *p == goal,
+        )
+        .unwrap();
+        assert_eq!(path.cost, 10);
+        assert_eq!(path.states, vec![Point::new(3, 0), goal]);
+    }
+}
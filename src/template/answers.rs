@@ -0,0 +1,313 @@
+/// Stores accepted answers per day and part, so solutions can be checked for regressions.
+use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{protocol::PartResult, Day, Year};
+
+fn get_answers_path(year: Year) -> String {
+    format!("./data/{year}/answers.json")
+}
+
+/// Accepted answers for a single day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Answer {
+    pub day: Day,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+impl Answer {
+    pub fn get(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// A recorded answer that a solution does not reproduce anymore.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Mismatch {
+    pub part: u8,
+    pub expected: String,
+    pub actual: Option<String>,
+}
+
+/// Accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        fs::create_dir_all(format!("./data/{year}"))?;
+        let mut file = fs::File::create(get_answers_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate answers of a year from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file(year: Year) -> Self {
+        let Ok(s) = fs::read_to_string(get_answers_path(year)) else {
+            return Answers::default();
+        };
+
+        match Answers::try_from(s) {
+            Ok(answers) => answers,
+            Err(e) => {
+                eprintln!("{e}");
+                Answers::default()
+            }
+        }
+    }
+
+    pub fn get(&self, day: Day) -> Option<&Answer> {
+        self.data.iter().find(|a| a.day == day)
+    }
+
+    /// Records the accepted answer for a part, overwriting a previous one.
+    pub fn set(&mut self, day: Day, part: u8, answer: &str) {
+        let index = match self.data.iter().position(|a| a.day == day) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    day,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.day);
+                self.data.iter().position(|a| a.day == day).unwrap()
+            }
+        };
+
+        let entry = &mut self.data[index];
+        match part {
+            1 => entry.part_1 = Some(answer.into()),
+            2 => entry.part_2 = Some(answer.into()),
+            _ => {}
+        }
+    }
+
+    /// Compares the results of running a day against its recorded answers.
+    pub fn compare(&self, day: Day, results: &[PartResult]) -> Vec<Mismatch> {
+        let Some(answer) = self.get(day) else {
+            return vec![];
+        };
+
+        [1, 2]
+            .into_iter()
+            .filter_map(|part| {
+                let expected = answer.get(part)?;
+                let actual = results
+                    .iter()
+                    .find(|r| r.part == part)
+                    .and_then(|r| r.answer.clone());
+
+                if actual.as_ref() == Some(expected) {
+                    None
+                } else {
+                    Some(Mismatch {
+                        part,
+                        expected: expected.clone(),
+                        actual,
+                    })
+                }
+            })
+            .collect()
+    }
+}
+
+/// Records an accepted answer in the answer store of its year.
+pub fn record(year: Year, day: Day, part: u8, answer: &str) -> Result<(), Error> {
+    let mut answers = Answers::read_from_file(year);
+    answers.set(day, part, answer);
+    answers.store_file(year)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected answer.day to be a Day struct.")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            day,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Answers, Mismatch};
+    use crate::{
+        day,
+        template::{protocol::PartResult, stats::Stats, PuzzleId},
+        year,
+    };
+
+    fn get_mock_result(part: u8, answer: Option<&str>) -> PartResult {
+        PartResult {
+            puzzle: PuzzleId::new(year!(2023), day!(3)),
+            part,
+            answer: answer.map(String::from),
+            error: None,
+            timed_out: false,
+            stats: Stats::from_samples(&[Duration::from_nanos(1)]),
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "day": "03", "part_1": "4361", "part_2": null }] }"#.to_string();
+        let answers = Answers::try_from(json).unwrap();
+        let answer = answers.get(day!(3)).unwrap();
+        assert_eq!(answer.part_1, Some("4361".to_string()));
+        assert_eq!(answer.part_2, None);
+    }
+
+    #[test]
+    fn sets_answers_in_day_order() {
+        let mut answers = Answers::default();
+        answers.set(day!(5), 1, "1");
+        answers.set(day!(3), 2, "2");
+        answers.set(day!(5), 1, "3");
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].day, day!(3));
+        assert_eq!(answers.data[0].part_2, Some("2".to_string()));
+        assert_eq!(answers.data[1].part_1, Some("3".to_string()));
+    }
+
+    #[test]
+    fn compares_results() {
+        let mut answers = Answers::default();
+        answers.set(day!(3), 1, "4361");
+        answers.set(day!(3), 2, "467835");
+
+        let matching = [
+            get_mock_result(1, Some("4361")),
+            get_mock_result(2, Some("467835")),
+        ];
+        assert_eq!(answers.compare(day!(3), &matching), vec![]);
+
+        let changed = [get_mock_result(1, Some("4360")), get_mock_result(2, None)];
+        assert_eq!(
+            answers.compare(day!(3), &changed),
+            vec![
+                Mismatch {
+                    part: 1,
+                    expected: "4361".into(),
+                    actual: Some("4360".into()),
+                },
+                Mismatch {
+                    part: 2,
+                    expected: "467835".into(),
+                    actual: None,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ignores_days_without_answers() {
+        let answers = Answers::default();
+        assert_eq!(
+            answers.compare(day!(3), &[get_mock_result(1, Some("1"))]),
+            vec![]
+        );
+    }
+}
@@ -0,0 +1,492 @@
+/// Native client for the Advent of Code website.
+/// Uses the session cookie directly to fetch inputs and puzzles and to submit answers.
+use std::{
+    env,
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::{Day, PuzzleId, Year};
+
+static DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+static SESSION_FILE_NAME: &str = ".adventofcode.session";
+static USER_AGENT: &str = "github.com/fspoettel/advent-of-code-rust";
+static LEADERBOARD_CACHE_TIME: Duration = Duration::from_secs(15 * 60);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    Http { status: u16, url: String },
+    Transport(String),
+    UnexpectedResponse(String),
+    InvalidLeaderboardId(String),
+    IO(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "session cookie not found. Set `AOC_SESSION` or create \"~/{SESSION_FILE_NAME}\"."
+            ),
+            AocClientError::Http { status, url } => {
+                write!(f, "request to \"{url}\" failed with status {status}.")
+            }
+            AocClientError::Transport(e) => write!(f, "request failed: {e}"),
+            AocClientError::UnexpectedResponse(e) => write!(f, "unexpected response: {e}"),
+            AocClientError::InvalidLeaderboardId(id) => write!(
+                f,
+                "`{id}` is not a leaderboard id, expected the number in the URL of the leaderboard."
+            ),
+            AocClientError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for AocClientError {}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::IO(e)
+    }
+}
+
+impl From<ureq::Error> for AocClientError {
+    fn from(e: ureq::Error) -> Self {
+        match e {
+            ureq::Error::Status(status, response) => AocClientError::Http {
+                status,
+                url: response.get_url().into(),
+            },
+            ureq::Error::Transport(transport) => AocClientError::Transport(transport.to_string()),
+        }
+    }
+}
+
+/// The verdict returned by the website after submitting an answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SubmissionOutcome {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// An answer was submitted too recently, the remaining cooldown is attached.
+    RateLimited(Duration),
+    /// The part was already solved or is not unlocked yet.
+    WrongLevel,
+}
+
+impl Display for SubmissionOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SubmissionOutcome::Correct => write!(f, "That's the right answer!"),
+            SubmissionOutcome::Incorrect => write!(f, "That's not the right answer."),
+            SubmissionOutcome::TooHigh => {
+                write!(f, "That's not the right answer, your answer is too high.")
+            }
+            SubmissionOutcome::TooLow => {
+                write!(f, "That's not the right answer, your answer is too low.")
+            }
+            SubmissionOutcome::RateLimited(wait) => write!(
+                f,
+                "You gave an answer too recently, wait {}s before trying again.",
+                wait.as_secs()
+            ),
+            SubmissionOutcome::WrongLevel => {
+                write!(f, "This part is either already solved or still locked.")
+            }
+        }
+    }
+}
+
+/// An authenticated client for a single year of advent of code.
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    year: Year,
+    agent: ureq::Agent,
+}
+
+impl AocClient {
+    pub fn new(session: &str, year: Year) -> Self {
+        Self {
+            base_url: DEFAULT_BASE_URL.into(),
+            session: session.trim().into(),
+            year,
+            agent: ureq::AgentBuilder::new().user_agent(USER_AGENT).build(),
+        }
+    }
+
+    /// Points the client at a different host, e.g. a local stand-in server.
+    #[must_use]
+    pub fn with_base_url(mut self, base_url: &str) -> Self {
+        self.base_url = base_url.trim_end_matches('/').into();
+        self
+    }
+
+    /// Creates a client for a year from the environment.
+    /// The session is read from `AOC_SESSION` or `~/.adventofcode.session`,
+    /// an optional base url from `AOC_BASE_URL`.
+    pub fn from_env(year: Year) -> Result<Self, AocClientError> {
+        let session = read_session().ok_or(AocClientError::SessionNotFound)?;
+
+        let client = Self::new(&session, year);
+
+        Ok(match env::var("AOC_BASE_URL") {
+            Ok(base_url) => client.with_base_url(&base_url),
+            Err(_) => client,
+        })
+    }
+
+    /// Fetches the puzzle input for a day.
+    pub fn get_input(&self, day: Day) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.day_url(day));
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call()?;
+        Ok(response.into_string()?)
+    }
+
+    /// Fetches the puzzle description for a day and converts it to markdown.
+    pub fn get_puzzle(&self, day: Day) -> Result<String, AocClientError> {
+        let url = self.day_url(day);
+        let html = self
+            .agent
+            .get(&url)
+            .set("Cookie", &self.cookie())
+            .call()?
+            .into_string()?;
+
+        let articles = extract_articles(&html);
+
+        if articles.is_empty() {
+            return Err(AocClientError::UnexpectedResponse(
+                "puzzle page does not contain a description.".into(),
+            ));
+        }
+
+        Ok(articles
+            .iter()
+            .map(|article| html2md::parse_html(article))
+            .collect::<Vec<_>>()
+            .join("\n\n"))
+    }
+
+    /// Submits an answer for one part of a day.
+    pub fn submit(
+        &self,
+        day: Day,
+        part: u8,
+        answer: &str,
+    ) -> Result<SubmissionOutcome, AocClientError> {
+        let url = format!("{}/answer", self.day_url(day));
+        let html = self
+            .agent
+            .post(&url)
+            .set("Cookie", &self.cookie())
+            .send_form(&[("level", &part.to_string()), ("answer", answer)])?
+            .into_string()?;
+
+        let article = extract_articles(&html).into_iter().next().ok_or_else(|| {
+            AocClientError::UnexpectedResponse("answer page does not contain a verdict.".into())
+        })?;
+
+        parse_submission_outcome(&strip_tags(article)).ok_or_else(|| {
+            AocClientError::UnexpectedResponse(format!("unknown verdict: {}", strip_tags(article)))
+        })
+    }
+
+    /// Fetches the JSON of a private leaderboard. `id` is the id of the leaderboard's owner.
+    pub fn get_leaderboard(&self, id: &str) -> Result<String, AocClientError> {
+        let url = format!(
+            "{}/{}/leaderboard/private/view/{id}.json",
+            self.base_url, self.year
+        );
+        let response = self.agent.get(&url).set("Cookie", &self.cookie()).call()?;
+        Ok(response.into_string()?)
+    }
+
+    fn day_url(&self, day: Day) -> String {
+        format!("{}/{}/day/{}", self.base_url, self.year, day.into_inner())
+    }
+
+    fn cookie(&self) -> String {
+        format!("session={}", self.session)
+    }
+}
+
+pub fn get_input_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("inputs", "txt")
+}
+
+pub fn get_puzzle_path(puzzle: PuzzleId) -> String {
+    puzzle.data_path("puzzles", "md")
+}
+
+/// Reads the title of a puzzle from its stored description, e.g. `Trebuchet?!`.
+pub fn read_title(puzzle: PuzzleId) -> Option<String> {
+    extract_title(&fs::read_to_string(get_puzzle_path(puzzle)).ok()?)
+}
+
+/// Downloads input and puzzle description for a day to the `data` folder.
+pub fn download(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(puzzle.year)?;
+
+    let input_path = get_input_path(puzzle);
+    let puzzle_path = get_puzzle_path(puzzle);
+
+    write_file(&input_path, &client.get_input(puzzle.day)?)?;
+    write_file(&puzzle_path, &client.get_puzzle(puzzle.day)?)?;
+
+    println!("---");
+    println!("🎄 Successfully wrote input to \"{}\".", &input_path);
+    println!("🎄 Successfully wrote puzzle to \"{}\".", &puzzle_path);
+    Ok(())
+}
+
+/// Fetches the JSON of a private leaderboard.
+/// The website asks to not fetch leaderboards more often than every 15 minutes, so a stored copy in
+/// `data/<year>/leaderboards` is reused until it is that old.
+pub fn fetch_leaderboard(year: Year, id: &str) -> Result<String, AocClientError> {
+    // the id is part of the path of the stored copy.
+    if id.is_empty() || !id.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(AocClientError::InvalidLeaderboardId(id.into()));
+    }
+
+    let path = format!("data/{year}/leaderboards/{id}.json");
+
+    let is_fresh = fs::metadata(&path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age < LEADERBOARD_CACHE_TIME);
+
+    if is_fresh {
+        return Ok(fs::read_to_string(&path)?);
+    }
+
+    let json = AocClient::from_env(year)?.get_leaderboard(id)?;
+    write_file(&path, &json)?;
+    Ok(json)
+}
+
+/// Fetches the puzzle description for a day, stores it and prints it to stdout.
+pub fn read(puzzle: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env(puzzle.year)?;
+    let description = client.get_puzzle(puzzle.day)?;
+    write_file(&get_puzzle_path(puzzle), &description)?;
+    println!("{description}");
+    Ok(())
+}
+
+pub fn submit(
+    puzzle: PuzzleId,
+    part: u8,
+    result: &str,
+) -> Result<SubmissionOutcome, AocClientError> {
+    AocClient::from_env(puzzle.year)?.submit(puzzle.day, part, result)
+}
+
+/// Writes a file, creating its parent directories if necessary.
+fn write_file(path: &str, contents: &str) -> Result<(), io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+fn read_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        return Some(session);
+    }
+
+    let home = env::var("HOME").or_else(|_| env::var("USERPROFILE")).ok()?;
+    fs::read_to_string(PathBuf::from(home).join(SESSION_FILE_NAME)).ok()
+}
+
+/// Finds the title in a heading like `--- Day 1: Trebuchet?! ---` of a puzzle description.
+fn extract_title(markdown: &str) -> Option<String> {
+    let heading = markdown.lines().find(|line| line.contains("--- Day "))?;
+    let (_, title) = heading.split_once(": ")?;
+    // markdown escapes are not part of the title.
+    let title = title.trim().trim_end_matches('-').trim().replace('\\', "");
+    (!title.is_empty()).then_some(title)
+}
+
+/// Returns the inner html of every `<article>` element in a page.
+fn extract_articles(html: &str) -> Vec<&str> {
+    html.split("<article")
+        .skip(1)
+        .filter_map(|s| {
+            let (_, rest) = s.split_once('>')?;
+            let (inner, _) = rest.split_once("</article>")?;
+            Some(inner)
+        })
+        .collect()
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::with_capacity(html.len());
+    let mut in_tag = false;
+
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+
+    text
+}
+
+fn parse_submission_outcome(text: &str) -> Option<SubmissionOutcome> {
+    if text.contains("That's the right answer") {
+        Some(SubmissionOutcome::Correct)
+    } else if text.contains("your answer is too high") {
+        Some(SubmissionOutcome::TooHigh)
+    } else if text.contains("your answer is too low") {
+        Some(SubmissionOutcome::TooLow)
+    } else if text.contains("That's not the right answer") {
+        Some(SubmissionOutcome::Incorrect)
+    } else if text.contains("You gave an answer too recently") {
+        Some(SubmissionOutcome::RateLimited(parse_wait_time(text)))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Some(SubmissionOutcome::WrongLevel)
+    } else {
+        None
+    }
+}
+
+/// Parses a remaining cooldown like `You have 1m 23s left to wait.`.
+fn parse_wait_time(text: &str) -> Duration {
+    let secs = text
+        .split("You have ")
+        .nth(1)
+        .and_then(|s| s.split(" left to wait").next())
+        .map_or(0, |s| {
+            s.split_whitespace()
+                .filter_map(|token| {
+                    let (value, unit) = token.split_at(token.len().checked_sub(1)?);
+                    let value = value.parse::<u64>().ok()?;
+                    match unit {
+                        "h" => Some(value * 3600),
+                        "m" => Some(value * 60),
+                        "s" => Some(value),
+                        _ => None,
+                    }
+                })
+                .sum()
+        });
+
+    Duration::from_secs(secs)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{
+        io::{BufRead, BufReader, Write},
+        net::TcpListener,
+        thread,
+        time::Duration,
+    };
+
+    use super::{
+        extract_articles, extract_title, fetch_leaderboard, parse_submission_outcome, AocClient,
+        AocClientError, SubmissionOutcome,
+    };
+    use crate::{day, year};
+
+    #[test]
+    fn extracts_articles() {
+        let html = r#"<main><article class="day-desc"><h2>--- Day 1 ---</h2></article><p>x</p><article class="day-desc"><p>part two</p></article></main>"#;
+        assert_eq!(
+            extract_articles(html),
+            vec!["<h2>--- Day 1 ---</h2>", "<p>part two</p>"]
+        );
+    }
+
+    #[test]
+    fn extracts_titles() {
+        let markdown = "\\--- Day 1: Trebuchet?! ---\n----------\n\nYou try to *ask*";
+        assert_eq!(extract_title(markdown), Some("Trebuchet?!".into()));
+        assert_eq!(extract_title("\\--- Part Two ---"), None);
+    }
+
+    #[test]
+    fn rejects_invalid_leaderboard_ids() {
+        for id in ["", "../../x", "123/456", "12a"] {
+            assert!(matches!(
+                fetch_leaderboard(year!(2023), id),
+                Err(AocClientError::InvalidLeaderboardId(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn parses_submission_outcomes() {
+        assert_eq!(
+            parse_submission_outcome("That's the right answer! You are one gold star closer."),
+            Some(SubmissionOutcome::Correct)
+        );
+        assert_eq!(
+            parse_submission_outcome("That's not the right answer; your answer is too high."),
+            Some(SubmissionOutcome::TooHigh)
+        );
+        assert_eq!(
+            parse_submission_outcome("That's not the right answer; your answer is too low."),
+            Some(SubmissionOutcome::TooLow)
+        );
+        assert_eq!(
+            parse_submission_outcome("That's not the right answer. If you're stuck..."),
+            Some(SubmissionOutcome::Incorrect)
+        );
+        assert_eq!(
+            parse_submission_outcome(
+                "You gave an answer too recently. You have 1m 23s left to wait."
+            ),
+            Some(SubmissionOutcome::RateLimited(Duration::from_secs(83)))
+        );
+        assert_eq!(
+            parse_submission_outcome("You don't seem to be solving the right level."),
+            Some(SubmissionOutcome::WrongLevel)
+        );
+        assert_eq!(parse_submission_outcome("???"), None);
+    }
+
+    #[test]
+    fn fetches_input_from_base_url() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = vec![];
+            for line in BufReader::new(&stream).lines() {
+                let line = line.unwrap();
+                if line.is_empty() {
+                    break;
+                }
+                request.push(line);
+            }
+            let body = "1\n2\n3\n";
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+            request
+        });
+
+        let client = AocClient::new("secret", year!(2023)).with_base_url(&format!("http://{addr}"));
+        let input = client.get_input(day!(1)).unwrap();
+        let request = server.join().unwrap();
+
+        assert_eq!(input, "1\n2\n3\n");
+        assert_eq!(request[0], "GET /2023/day/1/input HTTP/1.1");
+        assert!(request.iter().any(|l| l == "Cookie: session=secret"));
+    }
+}
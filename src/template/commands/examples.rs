@@ -0,0 +1,64 @@
+use std::{fs, path::Path, process};
+
+use crate::template::{
+    aoc_client,
+    examples::{extract_cases, format_cases, merge_cases, parse_cases},
+    PuzzleId,
+};
+
+pub fn handle(puzzle: PuzzleId) {
+    let puzzle_path = aoc_client::get_puzzle_path(puzzle);
+    let Ok(markdown) = fs::read_to_string(&puzzle_path) else {
+        eprintln!(
+            "Puzzle description \"{puzzle_path}\" not found. Download it first with `cargo download {} --year {}`.",
+            puzzle.day, puzzle.year
+        );
+        process::exit(1);
+    };
+
+    let extracted = extract_cases(&markdown);
+    if extracted.is_empty() {
+        eprintln!("No examples found in \"{puzzle_path}\".");
+        process::exit(1);
+    }
+
+    let cases_path = puzzle.data_path("examples", "cases");
+    let mut cases = match fs::read_to_string(&cases_path) {
+        Ok(contents) => parse_cases(&contents).unwrap_or_else(|e| {
+            eprintln!("Failed to parse \"{cases_path}\": {e}");
+            process::exit(1);
+        }),
+        Err(_) => vec![],
+    };
+
+    merge_cases(&mut cases, extracted);
+
+    if let Some(parent) = Path::new(&cases_path).parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+
+    if let Err(e) = fs::write(&cases_path, format_cases(&cases)) {
+        eprintln!("Failed to write example cases: {e}");
+        process::exit(1);
+    }
+
+    println!("Wrote {} example case(s) to \"{cases_path}\"", cases.len());
+    for case in &cases {
+        println!(
+            "  {}: part 1 = {}, part 2 = {}",
+            case.name,
+            case.part_1.as_deref().unwrap_or("?"),
+            case.part_2.as_deref().unwrap_or("?")
+        );
+    }
+
+    // the tests of the template read the example file, fill it with the main example.
+    let example_path = puzzle.data_path("examples", "txt");
+    let is_empty = fs::read_to_string(&example_path).map_or(true, |s| s.trim().is_empty());
+    if is_empty {
+        match fs::write(&example_path, &cases[0].input) {
+            Ok(()) => println!("Wrote first example to \"{example_path}\""),
+            Err(e) => eprintln!("Failed to write example file: {e}"),
+        }
+    }
+}
@@ -0,0 +1,41 @@
+use std::{env, fs, process};
+
+use crate::template::leaderboard::{render_day, render_overview, Leaderboard};
+use crate::template::{aoc_client, Day, Year};
+
+/// Prints a private leaderboard, read from `file` or fetched by the id of its owner.
+/// The id defaults to the `AOC_LEADERBOARD_ID` environment variable.
+pub fn handle(year: Year, id: Option<String>, file: Option<String>, day: Option<Day>) {
+    let json = match (file, id.or_else(|| env::var("AOC_LEADERBOARD_ID").ok())) {
+        (Some(file), _) => fs::read_to_string(&file).unwrap_or_else(|e| {
+            eprintln!("Failed to read leaderboard from \"{file}\": {e}");
+            process::exit(1);
+        }),
+        (None, Some(id)) => aoc_client::fetch_leaderboard(year, &id).unwrap_or_else(|e| {
+            eprintln!("Failed to fetch leaderboard: {e}");
+            process::exit(1);
+        }),
+        (None, None) => {
+            eprintln!(
+                "No leaderboard specified. Pass an id, `--file` or set `AOC_LEADERBOARD_ID`."
+            );
+            process::exit(1);
+        }
+    };
+
+    let leaderboard = match Leaderboard::try_from(json) {
+        Ok(leaderboard) => leaderboard,
+        Err(e) => {
+            eprintln!("Failed to parse leaderboard: {e}");
+            process::exit(1);
+        }
+    };
+
+    print!("{}", render_overview(&leaderboard));
+
+    // show the times of the latest day by default.
+    if let Some(day) = day.or_else(|| leaderboard.last_day()) {
+        println!();
+        print!("{}", render_day(&leaderboard, day));
+    }
+}
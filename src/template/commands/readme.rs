@@ -0,0 +1,26 @@
+use std::process;
+
+use crate::template::timings::Timings;
+use crate::template::{readme_benchmarks, readme_stars, Year};
+
+/// Updates the stars table and, if timings were stored, the benchmark table of a year.
+pub fn handle(year: Year) {
+    match readme_stars::update(year) {
+        Ok(true) => {}
+        Ok(false) => println!("No recorded progress for {year}, keeping the stars table."),
+        Err(e) => {
+            eprintln!("Failed to update stars: {e:?}");
+            process::exit(1);
+        }
+    }
+
+    let timings = Timings::read_from_file(year);
+    if !timings.data.is_empty() {
+        if let Err(e) = readme_benchmarks::update(year, timings) {
+            eprintln!("Failed to update benchmarks: {e:?}");
+            process::exit(1);
+        }
+    }
+
+    println!("Updated readme for {year}.");
+}
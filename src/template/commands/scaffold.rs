@@ -1,26 +1,77 @@
 use std::{
-    fs::{File, OpenOptions},
+    fs::{self, File, OpenOptions},
     io::Write,
+    path::Path,
     process,
 };
 
-use crate::template::Day;
+use crate::template::{aoc_client, PuzzleId};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
+/// User templates live in `templates/<name>.rs` and take precedence over the built-in template.
+const TEMPLATES_DIR: &str = "templates";
+const DEFAULT_TEMPLATE_NAME: &str = "default";
+const DEFAULT_ANSWER_TYPE: &str = "u32";
+
+/// Loads a user template by name. Without a name, `templates/default.rs` is used if present,
+/// falling back to the built-in template.
+fn load_template(name: Option<&str>) -> Result<String, String> {
+    let path =
+        Path::new(TEMPLATES_DIR).join(format!("{}.rs", name.unwrap_or(DEFAULT_TEMPLATE_NAME)));
+
+    match fs::read_to_string(&path) {
+        Ok(template) => Ok(template),
+        Err(_) if name.is_none() => Ok(MODULE_TEMPLATE.into()),
+        Err(e) => Err(format!(
+            "could not read template \"{}\": {e}",
+            path.display()
+        )),
+    }
+}
+
+/// Replaces the placeholders of a template:
+/// - `%YEAR%`: the year, e.g. `2023`.
+/// - `%DAY%`: the zero-padded day, e.g. `01`.
+/// - `%DAY_NUMBER%`: the day without padding, e.g. `1`.
+/// - `%TITLE%`: the puzzle title if the description was downloaded, e.g. `Trebuchet?!`.
+/// - `%ANSWER_TYPE%`: the return type of the parts, e.g. `u32`.
+fn render_template(template: &str, puzzle: PuzzleId, answer_type: &str) -> String {
+    let day_number = puzzle.day.into_inner().to_string();
+    let title = aoc_client::read_title(puzzle).unwrap_or_else(|| format!("Day {day_number}"));
+
+    template
+        .replace("%YEAR%", &puzzle.year.to_string())
+        .replace("%DAY_NUMBER%", &day_number)
+        .replace("%DAY%", &puzzle.day.to_string())
+        .replace("%TITLE%", &title)
+        .replace("%ANSWER_TYPE%", answer_type)
+}
+
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+    safe_create_file(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+pub fn handle(puzzle: PuzzleId, template: Option<&str>, answer_type: Option<&str>) {
+    let input_path = puzzle.data_path("inputs", "txt");
+    let example_path = puzzle.data_path("examples", "txt");
+    let module_path = puzzle.bin_path();
+
+    let template = match load_template(template) {
+        Ok(template) => template,
+        Err(e) => {
+            eprintln!("Failed to load template: {e}");
+            process::exit(1);
+        }
+    };
 
     let mut file = match safe_create_file(&module_path) {
         Ok(file) => file,
@@ -31,9 +82,12 @@ pub fn handle(day: Day) {
     };
 
     match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
+        render_template(
+            &template,
+            puzzle,
+            answer_type.unwrap_or(DEFAULT_ANSWER_TYPE),
+        )
+        .as_bytes(),
     ) {
         Ok(()) => {
             println!("Created module file \"{}\"", &module_path);
@@ -44,26 +98,35 @@ pub fn handle(day: Day) {
         }
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    // the input was downloaded already when scaffolding with `--download`.
+    if !Path::new(&input_path).exists() {
+        match create_file(&input_path) {
+            Ok(_) => {
+                println!("Created empty input file \"{}\"", &input_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create input file: {e}");
+                process::exit(1);
+            }
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+    // keep an example that was filled in already.
+    if !Path::new(&example_path).exists() {
+        match create_file(&example_path) {
+            Ok(_) => {
+                println!("Created empty example file \"{}\"", &example_path);
+            }
+            Err(e) => {
+                eprintln!("Failed to create example file: {e}");
+                process::exit(1);
+            }
         }
     }
 
     println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        puzzle.day, puzzle.year
+    );
 }
@@ -1,9 +1,18 @@
 use std::process::{Command, Stdio};
 
-use crate::template::Day;
+use crate::template::{visualize, PuzzleId};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+/// Runs the solution of a day. `input` is a path to read the input from instead of the puzzle input, `-` reads stdin.
+/// With `visualize`, the frames that the solution emits are shown.
+pub fn handle(
+    puzzle: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    input: Option<String>,
+    visualize: Option<visualize::Settings>,
+) {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), puzzle.to_string()];
 
     if dhat {
         cmd_args.extend([
@@ -23,6 +32,15 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
+    if let Some(input) = input {
+        cmd_args.push("--input".to_string());
+        cmd_args.push(input);
+    }
+
+    if let Some(settings) = visualize {
+        cmd_args.extend(settings.to_args());
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -1,34 +1,105 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process, time::Duration};
 
-use crate::template::run_multi::run_multi;
+use crate::template::history::{self, Run};
+use crate::template::regression::{find_regressions, Regression, Tolerance};
+use crate::template::run_multi::{measure_memory, run_multi};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{all_days, git, readme_benchmarks, Day, Limits, Year, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Flags of the `time` command.
+#[derive(Clone, Debug, Default)]
+pub struct Options {
+    /// Bench all days, including days with stored timings.
+    pub run_all: bool,
+    pub store: bool,
+    /// Revision to compare against.
+    pub compare: Option<String>,
+    pub check: Option<Tolerance>,
+    /// Also record heap usage with dhat.
+    pub memory: bool,
+    pub limits: Limits,
+    /// Path to read the input of the day from instead of the puzzle input, `-` for stdin.
+    pub input: Option<String>,
+}
+
+pub fn handle(year: Year, day: Option<Day>, options: Options) {
+    let Options {
+        run_all,
+        store,
+        compare,
+        check,
+        memory,
+        limits,
+        input,
+    } = options;
+
+    if input.is_some() {
+        // timings of another input are not comparable to the stored ones.
+        if day.is_none() || store || compare.is_some() || check.is_some() || memory {
+            eprintln!("`--input` requires a day and can not be combined with `--store`, `--compare`, `--check` or `--memory`.");
+            process::exit(1);
+        }
+    }
+
+    let stored_timings = Timings::read_from_file(year);
+
+    let previous = match &compare {
+        Some(rev) => match find_previous_run(year, rev) {
+            Some(run) => Some(run),
+            None => process::exit(1),
+        },
+        None => None,
+    };
 
     let days_to_run = day.map_or_else(
         || {
-            if run_all {
+            // a check needs to bench days that are stored already.
+            if run_all || check.is_some() {
                 all_days().collect()
             } else {
-                // when the `--all` flag is not set, filter out days that are fully benched.
+                // when the `--all` flag is not set, filter out days that are fully benched,
+                // unless they are needed for the comparison.
                 all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                    .filter(|day| {
+                        !stored_timings.is_day_complete(*day)
+                            || previous.as_ref().is_some_and(|run| run.days.contains(day))
+                    })
                     .collect()
             }
         },
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let mut timings =
+        run_multi(year, &days_to_run, true, true, 1, limits, input.as_deref()).unwrap();
+
+    if memory {
+        measure_memory(year, &mut timings, limits);
+    }
+
+    if let (Some(rev), Some(previous)) = (compare, previous) {
+        print_comparison(&rev, &previous, &timings);
+    }
+
+    if let Some(tolerance) = check {
+        let regressions = find_regressions(&stored_timings, &timings, tolerance);
+        if !regressions.is_empty() {
+            print_regressions(&regressions);
+            process::exit(1);
+        }
+        println!("\nNo regressions compared to stored timings.");
+    }
 
     if store {
+        if let Err(e) = history::append(year, &Run::new(timings.clone())) {
+            eprintln!("Failed to append to benchmark history: {e}");
+        }
+
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file(year).unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        match readme_benchmarks::update(year, merged_timings) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -38,3 +109,88 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         }
     }
 }
+
+/// Finds the latest stored run of `rev` to compare against.
+fn find_previous_run(year: Year, rev: &str) -> Option<Run> {
+    let runs = match history::read(year) {
+        Ok(runs) => runs,
+        Err(e) => {
+            eprintln!("Failed to read benchmark history: {e}");
+            return None;
+        }
+    };
+
+    let run = history::find_latest(&runs, &git::resolve_revision(rev)).cloned();
+    if run.is_none() {
+        eprintln!("No stored benchmark run found for `{rev}`.");
+    }
+    run
+}
+
+fn print_comparison(rev: &str, previous: &Run, timings: &Timings) {
+    println!("\n{ANSI_BOLD}Compared to {rev}{ANSI_RESET}");
+    println!("------");
+
+    let changes = history::compare(&previous.timings, timings);
+    if changes.is_empty() {
+        println!("No parts were benchmarked in both runs.");
+    }
+
+    for change in changes {
+        let speedup = change.speedup();
+        let verdict = if speedup >= 1.0 {
+            format!("{speedup:.2}x faster")
+        } else {
+            format!("{:.2}x slower", 1.0 / speedup)
+        };
+
+        println!(
+            "Day {} Part {}: {:.1?} -> {:.1?} ({verdict})",
+            change.day, change.part, change.before, change.after
+        );
+    }
+
+    let not_rerun = previous
+        .days
+        .iter()
+        .filter(|day| !timings.data.iter().any(|t| t.day == **day))
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    if !not_rerun.is_empty() {
+        println!("Not re-run: days {}", not_rerun.join(", "));
+    }
+
+    let not_stored = timings
+        .data
+        .iter()
+        .filter(|t| !previous.days.contains(&t.day))
+        .map(|t| t.day.to_string())
+        .collect::<Vec<_>>();
+    if !not_stored.is_empty() {
+        println!("Not in the run of {rev}: days {}", not_stored.join(", "));
+    }
+}
+
+fn print_regressions(regressions: &[Regression]) {
+    println!("\n{ANSI_BOLD}Regressions{ANSI_RESET}");
+    println!("------");
+    println!("| Day | Part | Stored | Current | Change |");
+    println!("| :---: | :---: | ---: | ---: | ---: |");
+
+    for regression in regressions {
+        let part = regression
+            .part
+            .map_or_else(|| "Total".into(), |part| part.to_string());
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let stored = Duration::from_nanos(regression.stored_nanos as u64);
+        let change = regression
+            .percent()
+            .map_or_else(|| "-".into(), |percent| format!("+{percent:.1}%"));
+
+        println!(
+            "| {} | {part} | {stored:.1?} | {} | {change} |",
+            regression.day, regression.current
+        );
+    }
+}
@@ -0,0 +1,65 @@
+use std::{io, process};
+
+use crate::template::answers::Answers;
+use crate::template::run_multi::child_commands;
+use crate::template::{Limits, PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+pub fn handle(year: Year) {
+    let answers = Answers::read_from_file(year);
+
+    if answers.data.is_empty() {
+        println!("No recorded answers for {year}.");
+        return;
+    }
+
+    let mut failed = vec![];
+
+    for answer in &answers.data {
+        let puzzle = PuzzleId::new(year, answer.day);
+
+        println!("{ANSI_BOLD}Day {}{ANSI_RESET}", answer.day);
+        println!("------");
+
+        let results = match child_commands::run_solution(
+            puzzle,
+            false,
+            true,
+            Limits::default(),
+            None,
+            &mut io::stdout(),
+            &mut io::stderr(),
+        ) {
+            Ok(results) => results,
+            Err(e) => {
+                eprintln!("failed to run solution: {e}");
+                failed.push(puzzle);
+                continue;
+            }
+        };
+
+        let mismatches = answers.compare(answer.day, &results);
+
+        for mismatch in &mismatches {
+            println!(
+                "Part {}: expected `{}`, got `{}`",
+                mismatch.part,
+                mismatch.expected,
+                mismatch.actual.as_deref().unwrap_or("✖")
+            );
+        }
+
+        if !mismatches.is_empty() {
+            failed.push(puzzle);
+        }
+
+        println!();
+    }
+
+    if failed.is_empty() {
+        println!("🎄 All recorded answers verified.");
+    } else {
+        let days: Vec<String> = failed.iter().map(|p| p.day.to_string()).collect();
+        eprintln!("Answers changed for day(s): {}", days.join(", "));
+        process::exit(1);
+    }
+}
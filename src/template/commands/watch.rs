@@ -0,0 +1,327 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    io::{BufRead, BufReader},
+    path::{Path, PathBuf},
+    process::{self, Child, Command, Stdio},
+    str::FromStr,
+    thread::{self, JoinHandle},
+    time::{Duration, Instant, SystemTime},
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(200);
+/// Editors often write a file several times on save, wait for changes to settle before restarting.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+/// Steps that run after every change, in order.
+/// Binaries are built with cargo and then run directly, so a run can be killed without leaving
+/// a process behind that cargo spawned.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Step {
+    BuildTests,
+    RunTests,
+    BuildSolution,
+    RunSolution,
+}
+
+struct Running {
+    step: Step,
+    child: Child,
+    /// Reads the output of the step. Build steps yield the path of the built binary.
+    reader: JoinHandle<Option<PathBuf>>,
+}
+
+impl Running {
+    fn stop(mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+        let _ = self.reader.join();
+    }
+}
+
+/// Modification times of the watched files.
+type Snapshot = BTreeMap<PathBuf, Option<SystemTime>>;
+
+/// Files a day depends on: its solution, the library with all shared modules, the manifest and its data files.
+/// Listed again on every poll, so added or removed modules count as changes.
+fn watched_paths(puzzle: PuzzleId) -> Vec<PathBuf> {
+    let mut paths = vec![];
+    collect_sources(Path::new("src"), &mut paths);
+
+    paths.extend(
+        [
+            puzzle.bin_path(),
+            "Cargo.toml".into(),
+            puzzle.data_path("inputs", "txt"),
+            puzzle.data_path("examples", "txt"),
+            puzzle.data_path("examples", "cases"),
+        ]
+        .into_iter()
+        .map(PathBuf::from),
+    );
+    paths
+}
+
+fn collect_sources(dir: &Path, paths: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+
+    for path in entries.flatten().map(|entry| entry.path()) {
+        // solutions of other days do not affect this one.
+        if path == Path::new("src/bin") {
+            continue;
+        }
+
+        if path.is_dir() {
+            collect_sources(&path, paths);
+        } else {
+            paths.push(path);
+        }
+    }
+}
+
+fn snapshot(puzzle: PuzzleId) -> Snapshot {
+    watched_paths(puzzle)
+        .into_iter()
+        .map(|path| {
+            let modified = path.metadata().and_then(|m| m.modified()).ok();
+            (path, modified)
+        })
+        .collect()
+}
+
+pub fn handle(puzzle: PuzzleId, release: bool) {
+    let mut last_snapshot = snapshot(puzzle);
+    let mut running = restart(puzzle, None);
+
+    loop {
+        thread::sleep(POLL_INTERVAL);
+
+        let current = snapshot(puzzle);
+        if current != last_snapshot {
+            last_snapshot = wait_until_settled(puzzle, current);
+            running = restart(puzzle, running);
+            continue;
+        }
+
+        if let Some(mut step) = running.take() {
+            running = match step.child.try_wait() {
+                Ok(None) => Some(step),
+                Ok(Some(status)) => {
+                    let executable = step.reader.join().unwrap_or(None);
+                    advance(puzzle, release, step.step, status.success(), executable)
+                }
+                Err(e) => {
+                    eprintln!("Failed to wait for {:?}: {e}", step.step);
+                    None
+                }
+            };
+        }
+    }
+}
+
+fn wait_until_settled(puzzle: PuzzleId, mut current: Snapshot) -> Snapshot {
+    let mut changed_at = Instant::now();
+
+    while changed_at.elapsed() < DEBOUNCE {
+        thread::sleep(POLL_INTERVAL.min(DEBOUNCE));
+        let next = snapshot(puzzle);
+        if next != current {
+            current = next;
+            changed_at = Instant::now();
+        }
+    }
+
+    current
+}
+
+fn restart(puzzle: PuzzleId, running: Option<Running>) -> Option<Running> {
+    if let Some(running) = running {
+        running.stop();
+    }
+
+    print!("{ANSI_CLEAR}");
+    println!("{ANSI_BOLD}Watching {puzzle}{ANSI_RESET} (press Ctrl-C to stop)");
+    println!("------");
+
+    start(Step::BuildTests, puzzle, false, None)
+}
+
+/// Starts the step after `step` finished.
+fn advance(
+    puzzle: PuzzleId,
+    release: bool,
+    step: Step,
+    success: bool,
+    executable: Option<PathBuf>,
+) -> Option<Running> {
+    match step {
+        Step::BuildTests => match executable {
+            Some(executable) if success => {
+                start(Step::RunTests, puzzle, release, Some(&executable))
+            }
+            _ => {
+                println!("Build failed, waiting for changes...");
+                None
+            }
+        },
+        Step::RunTests => {
+            if success {
+                println!("{ANSI_BOLD}Tests passed.{ANSI_RESET}\n");
+            } else {
+                println!("{ANSI_BOLD}Tests failed.{ANSI_RESET}\n");
+            }
+            start(Step::BuildSolution, puzzle, release, None)
+        }
+        Step::BuildSolution => match executable {
+            Some(executable) if success => {
+                start(Step::RunSolution, puzzle, release, Some(&executable))
+            }
+            _ => {
+                println!("Build failed, waiting for changes...");
+                None
+            }
+        },
+        Step::RunSolution => {
+            println!("\nWaiting for changes...");
+            None
+        }
+    }
+}
+
+fn start(
+    step: Step,
+    puzzle: PuzzleId,
+    release: bool,
+    executable: Option<&Path>,
+) -> Option<Running> {
+    let bin_name = puzzle.to_string();
+
+    let mut cmd = match (step, executable) {
+        (Step::BuildTests | Step::BuildSolution, _) => {
+            let mut cmd = Command::new("cargo");
+            if step == Step::BuildTests {
+                cmd.args(["test", "--no-run"]);
+            } else {
+                cmd.arg("build");
+                if release {
+                    cmd.arg("--release");
+                }
+            }
+            cmd.args([
+                "--quiet",
+                "--bin",
+                &bin_name,
+                "--message-format=json-render-diagnostics",
+            ]);
+            cmd
+        }
+        (Step::RunTests | Step::RunSolution, Some(executable)) => Command::new(executable),
+        (_, None) => unreachable!("{step:?} requires a built binary."),
+    };
+
+    let child = cmd
+        .stdout(if step == Step::RunSolution {
+            Stdio::inherit()
+        } else {
+            Stdio::piped()
+        })
+        .stderr(Stdio::inherit())
+        .spawn();
+
+    let mut child = match child {
+        Ok(child) => child,
+        Err(e) => {
+            eprintln!("Failed to start {step:?}: {e}");
+            process::exit(1);
+        }
+    };
+
+    let stdout = child.stdout.take();
+    let reader = thread::spawn(move || {
+        let lines = BufReader::new(stdout?).lines().map_while(Result::ok);
+        match step {
+            Step::BuildTests => find_executable(lines, &bin_name, true),
+            Step::BuildSolution => find_executable(lines, &bin_name, false),
+            Step::RunTests => {
+                lines.for_each(|line| print_test_line(&line));
+                None
+            }
+            Step::RunSolution => None,
+        }
+    });
+
+    Some(Running {
+        step,
+        child,
+        reader,
+    })
+}
+
+/// Finds the path of a built binary in the JSON messages of cargo.
+fn find_executable(
+    lines: impl Iterator<Item = String>,
+    bin_name: &str,
+    is_test: bool,
+) -> Option<PathBuf> {
+    let mut executable = None;
+
+    for line in lines {
+        let Ok(json) = JsonValue::from_str(&line) else {
+            continue;
+        };
+        let Some(message) = json.get::<HashMap<String, JsonValue>>() else {
+            continue;
+        };
+
+        let is_artifact = message
+            .get("reason")
+            .and_then(|v| v.get::<String>())
+            .is_some_and(|reason| reason == "compiler-artifact");
+
+        let is_target = message
+            .get("target")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .and_then(|target| target.get("name"))
+            .and_then(|v| v.get::<String>())
+            .is_some_and(|name| name == bin_name);
+
+        let is_test_profile = message
+            .get("profile")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .and_then(|profile| profile.get("test"))
+            .and_then(|v| v.get::<bool>())
+            .is_some_and(|test| *test);
+
+        if is_artifact && is_target && is_test_profile == is_test {
+            if let Some(path) = message.get("executable").and_then(|v| v.get::<String>()) {
+                executable = Some(PathBuf::from(path));
+            }
+        }
+    }
+
+    executable
+}
+
+/// Prints the output of the test binary, marking the result of every test.
+fn print_test_line(line: &str) {
+    let result = line
+        .strip_prefix("test ")
+        .and_then(|rest| rest.rsplit_once(" ... "));
+
+    match result {
+        Some((name, "ok")) => println!("  ✓ {}", name.trim_start_matches("tests::")),
+        Some((name, "FAILED")) => println!("  ✗ {}", name.trim_start_matches("tests::")),
+        Some((name, "ignored")) => println!("  - {}", name.trim_start_matches("tests::")),
+        _ if line.is_empty()
+            || line.starts_with("running ")
+            || line.starts_with("test result:") => {}
+        _ => println!("{line}"),
+    }
+}
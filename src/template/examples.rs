@@ -0,0 +1,267 @@
+/// Example cases with expected answers, read from `data/<year>/examples/<day>.cases`.
+///
+/// A cases file holds any number of named examples. Each case starts with a `>>> case: <name>` line,
+/// optionally followed by `>>> part_1: <answer>` and `>>> part_2: <answer>` lines. Everything after
+/// these headers up to the next case is the example input:
+///
+/// ```text
+/// >>> case: simple loop
+/// >>> part_1: 4
+/// .....
+/// .S-7.
+/// .|.|.
+/// .L-J.
+/// .....
+///
+/// >>> case: complex loop
+/// >>> part_1: 8
+/// ..F7.
+/// .FJ|.
+/// SJ.L7
+/// |F--J
+/// LJ...
+/// ```
+///
+/// The build script generates one test per declared answer, see [`crate::example_tests`].
+use std::{env, fs};
+
+use crate::template::{runner::PartOutput, PuzzleId};
+
+pub use cases::{format_cases, parse_cases, Case};
+
+// also included by the build script, so it must not depend on the rest of the crate.
+mod cases;
+
+/// Finds the code blocks and emphasized answers like `` `*142*` `` in a puzzle description.
+///
+/// Every answer is attached to the code block preceding it. Part two usually refers to the example of part one,
+/// so part two answers before any code block of part two are attached to the first code block.
+/// Code blocks without answers are skipped, except for the first one, which is the main example.
+pub fn extract_cases(markdown: &str) -> Vec<Case> {
+    let mut blocks: Vec<(String, [Option<String>; 2])> = vec![];
+    let mut part = 1;
+    let mut part_start = 0;
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        if line.trim_start().starts_with("```") {
+            match block.take() {
+                Some(input) => blocks.push((input, [None, None])),
+                None => block = Some(String::new()),
+            }
+            continue;
+        }
+
+        if let Some(input) = block.as_mut() {
+            input.push_str(line);
+            input.push('\n');
+            continue;
+        }
+
+        if line.contains("--- Part Two ---") {
+            part = 2;
+            part_start = blocks.len();
+            continue;
+        }
+
+        let Some(answer) = find_answers(line).last().cloned() else {
+            continue;
+        };
+
+        let target = if blocks.len() > part_start {
+            blocks.last_mut()
+        } else if part == 2 {
+            blocks.first_mut()
+        } else {
+            None
+        };
+
+        if let Some((_, answers)) = target {
+            answers[part - 1] = Some(answer);
+        }
+    }
+
+    blocks
+        .into_iter()
+        .enumerate()
+        .filter(|(i, (_, answers))| *i == 0 || answers.iter().any(Option::is_some))
+        .enumerate()
+        .map(|(n, (_, (input, [part_1, part_2])))| Case {
+            name: format!("example {}", n + 1),
+            part_1,
+            part_2,
+            input: format!("{}\n", input.trim_end()),
+        })
+        .collect()
+}
+
+/// Finds emphasized inline code like `` `*142*` ``, which is how puzzle descriptions highlight answers.
+fn find_answers(line: &str) -> Vec<String> {
+    let mut answers = vec![];
+    let mut rest = line;
+
+    while let Some(start) = rest.find("`*") {
+        let Some(end) = rest[start + 2..].find("*`") else {
+            break;
+        };
+        let answer = rest[start + 2..start + 2 + end].replace('\\', "");
+        if !answer.trim().is_empty() {
+            answers.push(answer.trim().to_string());
+        }
+        rest = &rest[start + 2 + end + 2..];
+    }
+
+    answers
+}
+
+/// Adds extracted cases to existing ones. Cases with the same input only gain answers they do not have yet,
+/// so extracting again after part two unlocked keeps manual edits.
+pub fn merge_cases(existing: &mut Vec<Case>, extracted: Vec<Case>) {
+    for case in extracted {
+        if let Some(known) = existing.iter_mut().find(|c| c.input == case.input) {
+            known.part_1 = known.part_1.take().or(case.part_1);
+            known.part_2 = known.part_2.take().or(case.part_2);
+            continue;
+        }
+
+        let mut n = existing.len() + 1;
+        while existing.iter().any(|c| c.name == format!("example {n}")) {
+            n += 1;
+        }
+
+        existing.push(Case {
+            name: format!("example {n}"),
+            ..case
+        });
+    }
+}
+
+/// Reads all example cases of a puzzle.
+pub fn read_cases(puzzle: PuzzleId) -> Vec<Case> {
+    let cwd = env::current_dir().unwrap();
+    let filepath = cwd.join(puzzle.data_path("examples", "cases"));
+    let contents = fs::read_to_string(filepath).expect("could not open cases file");
+    parse_cases(&contents).unwrap_or_else(|e| panic!("invalid cases file: {e}"))
+}
+
+/// Runs a solution part on an example case and asserts that it returns the expected answer.
+// Not part of the public API
+#[doc(hidden)]
+#[track_caller]
+pub fn __check_case<R: PartOutput>(
+    func: impl Fn(&str) -> R,
+    puzzle: PuzzleId,
+    name: &str,
+    part: u8,
+) {
+    let cases = read_cases(puzzle);
+    let case = cases
+        .iter()
+        .find(|case| case.name == name)
+        .unwrap_or_else(|| panic!("no example case named `{name}`."));
+
+    let actual = func(&case.input)
+        .to_answer()
+        .unwrap_or_else(|e| panic!("part {part} of example `{name}` failed: {e}"));
+    assert_eq!(
+        actual.as_ref(),
+        case.expected(part),
+        "unexpected answer for part {part} of example `{name}`."
+    );
+}
+
+/// Generates one `#[test]` per expected answer in the cases file of the current day.
+///
+/// Call it inside the `tests` module of a solution, after `use super::*;`.
+#[macro_export]
+macro_rules! example_tests {
+    () => {
+        include!(concat!(
+            env!("OUT_DIR"),
+            "/example_tests/",
+            env!("CARGO_BIN_NAME"),
+            ".rs"
+        ));
+    };
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{extract_cases, format_cases, merge_cases, parse_cases, Case};
+
+    const PUZZLE: &str = "\\--- Day 1: Trebuchet?! ---\n----------\n\nFor example:\n\n```\n1abc2\ntreb7uchet\n\n```\n\nAdding these together produces `*142*`.\n\n```\nan illustration\n\n```\n\nConsider your entire calibration document. *What is the sum?*\n\n\\--- Part Two ---\n----------\n\nIn the above example, the sum would be `12` + `*77*`.\n\n```\ntwo1nine\n\n```\n\nAdding these together produces `*281*`.\n";
+
+    #[test]
+    fn parses_cases() {
+        let cases = parse_cases(
+            ">>> case: first\n>>> part_1: 4\n>>> part_2: abc\nline 1\n\nline 2\n\n\n>>> case: second\n>>> part_2: 8\nother\n",
+        )
+        .unwrap();
+
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].name, "first");
+        assert_eq!(cases[0].part_1, Some("4".into()));
+        assert_eq!(cases[0].part_2, Some("abc".into()));
+        assert_eq!(cases[0].input, "line 1\n\nline 2\n");
+        assert_eq!(cases[1].part_1, None);
+        assert_eq!(cases[1].expected(2), Some(&"8".to_string()));
+        assert_eq!(cases[1].input, "other\n");
+    }
+
+    #[test]
+    fn treats_later_headers_as_input() {
+        let cases = parse_cases(">>> case: a\ninput\n>>> part_1: 1\n").unwrap();
+        assert_eq!(cases[0].part_1, None);
+        assert_eq!(cases[0].input, "input\n>>> part_1: 1\n");
+    }
+
+    #[test]
+    fn rejects_input_before_first_case() {
+        assert!(parse_cases("input\n>>> case: a\n").is_err());
+    }
+
+    #[test]
+    fn rejects_duplicate_names() {
+        assert!(parse_cases(">>> case: a\n1\n>>> case: a\n2\n").is_err());
+    }
+
+    #[test]
+    fn extracts_cases_from_puzzles() {
+        let cases = extract_cases(PUZZLE);
+        assert_eq!(
+            cases,
+            vec![
+                Case {
+                    name: "example 1".into(),
+                    part_1: Some("142".into()),
+                    part_2: Some("77".into()),
+                    input: "1abc2\ntreb7uchet\n".into(),
+                },
+                Case {
+                    name: "example 2".into(),
+                    part_1: None,
+                    part_2: Some("281".into()),
+                    input: "two1nine\n".into(),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn round_trips_cases() {
+        let cases = extract_cases(PUZZLE);
+        assert_eq!(parse_cases(&format_cases(&cases)).unwrap(), cases);
+    }
+
+    #[test]
+    fn merges_cases() {
+        let mut cases = parse_cases(">>> case: main\n>>> part_1: 1\n1abc2\ntreb7uchet\n").unwrap();
+        merge_cases(&mut cases, extract_cases(PUZZLE));
+
+        assert_eq!(cases.len(), 2);
+        assert_eq!(cases[0].name, "main");
+        assert_eq!(cases[0].part_1, Some("1".into()));
+        assert_eq!(cases[0].part_2, Some("77".into()));
+        assert_eq!(cases[1].name, "example 2");
+    }
+}
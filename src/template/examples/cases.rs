@@ -0,0 +1,100 @@
+//! Parsing and formatting of cases files, see `template::examples` for the format.
+
+const HEADER: &str = ">>> ";
+
+/// A named example input with optional expected answers.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Case {
+    pub name: String,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+    pub input: String,
+}
+
+impl Case {
+    pub fn expected(&self, part: u8) -> Option<&String> {
+        match part {
+            1 => self.part_1.as_ref(),
+            2 => self.part_2.as_ref(),
+            _ => None,
+        }
+    }
+}
+
+/// Parses the contents of a cases file.
+pub fn parse_cases(s: &str) -> Result<Vec<Case>, String> {
+    let mut cases: Vec<Case> = vec![];
+    let mut in_headers = false;
+
+    for line in s.lines() {
+        let header = line
+            .strip_prefix(HEADER)
+            .and_then(|header| header.split_once(':'))
+            .map(|(key, value)| (key.trim(), value.trim()));
+
+        match header {
+            Some(("case", name)) => {
+                if name.is_empty() {
+                    return Err("expected cases to have a name.".into());
+                }
+                if cases.iter().any(|case| case.name == name) {
+                    return Err(format!(
+                        "expected case names to be unique, found `{name}` twice."
+                    ));
+                }
+                cases.push(Case {
+                    name: name.into(),
+                    part_1: None,
+                    part_2: None,
+                    input: String::new(),
+                });
+                in_headers = true;
+            }
+            Some((key @ ("part_1" | "part_2"), value)) if in_headers => {
+                let case = cases.last_mut().unwrap();
+                if key == "part_1" {
+                    case.part_1 = Some(value.into());
+                } else {
+                    case.part_2 = Some(value.into());
+                }
+            }
+            _ => {
+                let Some(case) = cases.last_mut() else {
+                    if line.trim().is_empty() {
+                        continue;
+                    }
+                    return Err("expected file to start with `>>> case: <name>`.".into());
+                };
+                in_headers = false;
+                case.input.push_str(line);
+                case.input.push('\n');
+            }
+        }
+    }
+
+    // blank lines only separate cases and are not part of the input.
+    for case in &mut cases {
+        let len = case.input.trim_end().len();
+        case.input.truncate(len);
+        case.input.push('\n');
+    }
+
+    Ok(cases)
+}
+
+/// Serializes cases to the format read by [`parse_cases`].
+pub fn format_cases(cases: &[Case]) -> String {
+    cases
+        .iter()
+        .map(|case| {
+            let mut s = format!("{HEADER}case: {}\n", case.name);
+            for (part, answer) in [(1, &case.part_1), (2, &case.part_2)] {
+                if let Some(answer) = answer {
+                    s += &format!("{HEADER}part_{part}: {answer}\n");
+                }
+            }
+            s + &case.input
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
@@ -0,0 +1,104 @@
+/// Minimal read-only access to the git repository of the project.
+/// Reads refs straight from the `.git` directory instead of shelling out to `git`.
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Locates the directories holding refs, following `.git` files of worktrees and submodules.
+/// Worktrees keep their own `HEAD` but share other refs through a common directory.
+fn get_git_dirs() -> Option<Vec<PathBuf>> {
+    let dot_git = Path::new(".git");
+
+    let git_dir = if dot_git.is_file() {
+        let contents = fs::read_to_string(dot_git).ok()?;
+        PathBuf::from(contents.trim().strip_prefix("gitdir:")?.trim())
+    } else {
+        dot_git.to_path_buf()
+    };
+
+    match fs::read_to_string(git_dir.join("commondir")) {
+        Ok(common) => {
+            let common_dir = git_dir.join(common.trim());
+            Some(vec![git_dir, common_dir])
+        }
+        Err(_) => Some(vec![git_dir]),
+    }
+}
+
+fn is_commit_hash(s: &str) -> bool {
+    s.len() == 40 && s.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+/// Resolves a full ref name like `refs/heads/main` to a commit hash.
+fn resolve_ref(git_dirs: &[PathBuf], name: &str) -> Option<String> {
+    for git_dir in git_dirs {
+        if let Ok(contents) = fs::read_to_string(git_dir.join(name)) {
+            let contents = contents.trim();
+            return match contents.strip_prefix("ref:") {
+                Some(target) => resolve_ref(git_dirs, target.trim()),
+                None => is_commit_hash(contents).then(|| contents.to_string()),
+            };
+        }
+    }
+
+    git_dirs.iter().find_map(|git_dir| {
+        let packed = fs::read_to_string(git_dir.join("packed-refs")).ok()?;
+        parse_packed_refs(&packed, name)
+    })
+}
+
+fn parse_packed_refs(packed: &str, name: &str) -> Option<String> {
+    packed
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.starts_with('^'))
+        .find_map(|line| {
+            let (hash, ref_name) = line.split_once(' ')?;
+            (ref_name.trim() == name && is_commit_hash(hash)).then(|| hash.to_string())
+        })
+}
+
+/// Returns the hash of the commit that is currently checked out.
+pub fn head_commit() -> Option<String> {
+    resolve_ref(&get_git_dirs()?, "HEAD")
+}
+
+/// Resolves a revision given on the command-line.
+/// Branch and tag names resolve to their commit, anything else is returned as-is so
+/// it can be matched as a (possibly abbreviated) commit hash.
+pub fn resolve_revision(rev: &str) -> String {
+    let Some(git_dirs) = get_git_dirs() else {
+        return rev.to_string();
+    };
+
+    let candidates = [
+        rev.to_string(),
+        format!("refs/heads/{rev}"),
+        format!("refs/tags/{rev}"),
+        format!("refs/remotes/{rev}"),
+    ];
+
+    candidates
+        .iter()
+        .find_map(|name| resolve_ref(&git_dirs, name))
+        .unwrap_or_else(|| rev.to_lowercase())
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::parse_packed_refs;
+
+    #[test]
+    fn parses_packed_refs() {
+        let packed = "# pack-refs with: peeled fully-peeled sorted\n\
+            0123456789abcdef0123456789abcdef01234567 refs/heads/main\n\
+            89abcdef0123456789abcdef0123456789abcdef refs/tags/v1\n\
+            ^fedcba9876543210fedcba9876543210fedcba98\n";
+
+        assert_eq!(
+            parse_packed_refs(packed, "refs/tags/v1"),
+            Some("89abcdef0123456789abcdef0123456789abcdef".into())
+        );
+        assert_eq!(parse_packed_refs(packed, "refs/heads/other"), None);
+    }
+}
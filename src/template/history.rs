@@ -0,0 +1,318 @@
+/// History of stored benchmark runs, so solutions can be compared across commits.
+///
+/// Every `cargo time --store` appends one JSON object per line to `data/<year>/timings_history.jsonl`,
+/// tagged with the checked out commit, a timestamp and the host it ran on.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    str::FromStr,
+    time::Duration,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    git, submissions,
+    timings::{Timing, Timings},
+    Day, Year,
+};
+
+fn get_history_path(year: Year) -> String {
+    format!("./data/{year}/timings_history.jsonl")
+}
+
+/// A stored benchmark run.
+#[derive(Clone, Debug)]
+pub struct Run {
+    pub commit: Option<String>,
+    pub timestamp: u64,
+    pub host: String,
+    /// Days that were benchmarked in this run. Runs without `--all` only contain some days of a year.
+    pub days: Vec<Day>,
+    pub timings: Timings,
+}
+
+impl Run {
+    /// Tags timings of the current benchmark run with the checked out commit and this host.
+    pub fn new(timings: Timings) -> Self {
+        Run {
+            commit: git::head_commit(),
+            timestamp: submissions::now(),
+            host: get_host(),
+            days: timings.data.iter().map(|t| t.day).collect(),
+            timings,
+        }
+    }
+}
+
+fn get_host() -> String {
+    env::var("HOSTNAME")
+        .or_else(|_| env::var("COMPUTERNAME"))
+        .ok()
+        .or_else(|| fs::read_to_string("/etc/hostname").ok())
+        .map(|host| host.trim().to_string())
+        .filter(|host| !host.is_empty())
+        .unwrap_or_else(|| "unknown".into())
+}
+
+/// Appends a run to the history of its year.
+pub fn append(year: Year, run: &Run) -> Result<(), io::Error> {
+    let line = JsonValue::from(run)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    fs::create_dir_all(format!("./data/{year}"))?;
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(get_history_path(year))?;
+    writeln!(file, "{line}")
+}
+
+/// Reads all runs of a year, oldest first. A missing file yields no runs.
+pub fn read(year: Year) -> Result<Vec<Run>, String> {
+    let Ok(contents) = fs::read_to_string(get_history_path(year)) else {
+        return Ok(vec![]);
+    };
+
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(Run::from_str)
+        .collect()
+}
+
+/// Finds the latest run of a commit. `commit` may be abbreviated.
+pub fn find_latest<'a>(runs: &'a [Run], commit: &str) -> Option<&'a Run> {
+    runs.iter().rev().find(|run| {
+        run.commit
+            .as_ref()
+            .is_some_and(|c| !commit.is_empty() && c.starts_with(commit))
+    })
+}
+
+/// Change in median execution time of a part between two runs.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Change {
+    pub day: Day,
+    pub part: u8,
+    pub before: Duration,
+    pub after: Duration,
+}
+
+impl Change {
+    /// Factor by which the part got faster. Values below `1` are slowdowns.
+    pub fn speedup(&self) -> f64 {
+        self.before.as_secs_f64() / self.after.as_secs_f64()
+    }
+}
+
+/// Compares the parts that were benchmarked in both sets of timings.
+pub fn compare(before: &Timings, after: &Timings) -> Vec<Change> {
+    after
+        .data
+        .iter()
+        .filter_map(|new| Some((before.data.iter().find(|old| old.day == new.day)?, new)))
+        .flat_map(|(old, new)| {
+            [1, 2].into_iter().filter_map(|part| {
+                Some(Change {
+                    day: new.day,
+                    part,
+                    before: old.part_stats(part)?.median,
+                    after: new.part_stats(part)?.median,
+                })
+            })
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Run> for JsonValue {
+    fn from(value: &Run) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        #[allow(clippy::cast_precision_loss)]
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert("host".into(), JsonValue::String(value.host.clone()));
+        map.insert(
+            "days".into(),
+            JsonValue::Array(
+                value
+                    .days
+                    .iter()
+                    .map(|day| JsonValue::String(day.to_string()))
+                    .collect(),
+            ),
+        );
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.timings.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for Run {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("benchmark run is not valid JSON."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected benchmark run to be a JSON object.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected run.commit to be null or string.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected run.timestamp to be a number.")? as u64;
+
+        let host = json
+            .get("host")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected run.host to be a string.")?;
+
+        let data = json
+            .get("data")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected run.data to be an array.")?;
+
+        let timings = Timings {
+            data: data
+                .iter()
+                .map(Timing::try_from)
+                .collect::<Result<_, _>>()?,
+        };
+
+        // runs stored before days were recorded contain the days they have timings for.
+        let days = match json.get("days").and_then(|v| v.get::<Vec<JsonValue>>()) {
+            Some(days) => days
+                .iter()
+                .map(|day| day.get::<String>().and_then(|day| Day::from_str(day).ok()))
+                .collect::<Option<_>>()
+                .ok_or("Expected run.days to be an array of days.")?,
+            None => timings.data.iter().map(|t| t.day).collect(),
+        };
+
+        Ok(Run {
+            commit: commit.cloned(),
+            timestamp,
+            host: host.clone(),
+            days,
+            timings,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{str::FromStr, time::Duration};
+
+    use tinyjson::JsonValue;
+
+    use super::{compare, find_latest, Change, Run};
+    use crate::{
+        day,
+        template::{
+            stats::Stats,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn get_mock_timings(part_1_nanos: u64, part_2_nanos: Option<u64>) -> Timings {
+        let stats = |nanos| Stats::from_samples(&[Duration::from_nanos(nanos)]);
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some(format!("{part_1_nanos}ns")),
+                part_2: part_2_nanos.map(|x| format!("{x}ns")),
+                part_1_stats: Some(stats(part_1_nanos)),
+                part_2_stats: part_2_nanos.map(stats),
+                part_1_memory: None,
+                part_2_memory: None,
+                total_nanos: 0.0,
+                timed_out: None,
+                failed: vec![],
+            }],
+        }
+    }
+
+    fn get_mock_run(commit: &str, nanos: u64) -> Run {
+        Run {
+            commit: Some(commit.into()),
+            timestamp: 1_700_000_000,
+            host: "host".into(),
+            days: vec![day!(1)],
+            timings: get_mock_timings(nanos, None),
+        }
+    }
+
+    #[test]
+    fn round_trips_runs() {
+        let run = get_mock_run("abc123", 100);
+        let line = JsonValue::from(&run).stringify().unwrap();
+        let parsed = Run::from_str(&line).unwrap();
+        assert_eq!(parsed.commit, run.commit);
+        assert_eq!(parsed.timestamp, run.timestamp);
+        assert_eq!(parsed.host, run.host);
+        assert_eq!(parsed.days, run.days);
+        assert_eq!(
+            parsed.timings.data[0].part_1_stats,
+            run.timings.data[0].part_1_stats
+        );
+    }
+
+    #[test]
+    fn finds_latest_run_of_commit() {
+        let runs = vec![
+            get_mock_run("abc123", 100),
+            get_mock_run("def456", 200),
+            get_mock_run("abc123", 300),
+        ];
+        let run = find_latest(&runs, "abc").unwrap();
+        assert_eq!(
+            run.timings.data[0].part_1_stats.unwrap().median.as_nanos(),
+            300
+        );
+        assert!(find_latest(&runs, "fff").is_none());
+    }
+
+    #[test]
+    fn compares_parts_present_in_both_runs() {
+        let changes = compare(
+            &get_mock_timings(200, None),
+            &get_mock_timings(100, Some(50)),
+        );
+        assert_eq!(
+            changes,
+            vec![Change {
+                day: day!(1),
+                part: 1,
+                before: Duration::from_nanos(200),
+                after: Duration::from_nanos(100),
+            }]
+        );
+        assert_eq!(changes[0].speedup(), 2.0);
+    }
+}
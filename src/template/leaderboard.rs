@@ -0,0 +1,394 @@
+/// Private leaderboards, as served by `https://adventofcode.com/<year>/leaderboard/private/view/<id>.json`.
+use std::{
+    collections::{BTreeMap, HashMap},
+    fmt::Write,
+    str::FromStr,
+};
+use tinyjson::JsonValue;
+
+use crate::template::{Day, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Timestamps (seconds since the unix epoch) at which a member got the stars of a day.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Completion {
+    pub part_1: Option<u64>,
+    pub part_2: Option<u64>,
+}
+
+impl Completion {
+    pub fn stars(&self) -> usize {
+        usize::from(self.part_1.is_some()) + usize::from(self.part_2.is_some())
+    }
+
+    /// Seconds between the first and the second star.
+    pub fn delta(&self) -> Option<u64> {
+        Some(self.part_2?.saturating_sub(self.part_1?))
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Member {
+    pub id: u64,
+    pub name: Option<String>,
+    pub local_score: u64,
+    pub stars: u64,
+    pub completions: BTreeMap<Day, Completion>,
+}
+
+impl Member {
+    /// The name of the member, anonymous members are shown like on the website.
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+/// A private leaderboard of a year.
+/// Can be serialized from JSON.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Leaderboard {
+    pub year: Year,
+    pub owner_id: u64,
+    /// Members ordered by local score and stars, best first.
+    pub members: Vec<Member>,
+}
+
+impl Leaderboard {
+    /// The latest day that any member got a star for.
+    pub fn last_day(&self) -> Option<Day> {
+        self.members
+            .iter()
+            .filter_map(|m| m.completions.keys().max())
+            .max()
+            .copied()
+    }
+}
+
+/// Seconds since the unix epoch at which a puzzle unlocks, midnight EST (UTC-5) on December `day`.
+pub fn unlock_time(year: Year, day: Day) -> u64 {
+    // days from 1970-01-01 to <year>-12-<day>, see http://howardhinnant.github.io/date_algorithms.html#days_from_civil
+    let (y, m, d) = (
+        i64::from(year.into_inner()),
+        12,
+        i64::from(day.into_inner()),
+    );
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let doy = (153 * (m - 3) + 2) / 5 + d - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    let days = era * 146_097 + doe - 719_468;
+
+    u64::try_from(days * 86_400 + 5 * 3_600).unwrap_or(0)
+}
+
+/// Formats seconds like the website does, e.g. `01:02:03` or `>24h`.
+pub fn format_seconds(seconds: u64) -> String {
+    if seconds >= 24 * 3_600 {
+        return ">24h".into();
+    }
+    format!(
+        "{:02}:{:02}:{:02}",
+        seconds / 3_600,
+        seconds / 60 % 60,
+        seconds % 60
+    )
+}
+
+/// Renders one row per member with the stars of every day up to the latest solved one.
+pub fn render_overview(leaderboard: &Leaderboard) -> String {
+    let last_day = leaderboard.last_day().map_or(0, Day::into_inner);
+    let name_width = leaderboard
+        .members
+        .iter()
+        .map(|m| m.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    let mut s = format!(
+        "{ANSI_BOLD}{:>3}  {:<name_width$}  {:>5} ",
+        "#", "Name", "Score"
+    );
+    for day in 1..=last_day {
+        let _ = write!(s, "{day:>3}");
+    }
+    let _ = writeln!(s, "{ANSI_RESET}");
+
+    for (rank, member) in leaderboard.members.iter().enumerate() {
+        let _ = write!(
+            s,
+            "{:>3}) {:<name_width$}  {:>5} ",
+            rank + 1,
+            member.display_name(),
+            member.local_score
+        );
+
+        for day in 1..=last_day {
+            let stars = Day::new(day)
+                .and_then(|day| member.completions.get(&day))
+                .map_or(0, Completion::stars);
+            let symbol = match stars {
+                2 => "★",
+                1 => "☆",
+                _ => "·",
+            };
+            let _ = write!(s, "  {symbol}");
+        }
+
+        s.push('\n');
+    }
+
+    s
+}
+
+/// Renders the times of the members that solved a day, with the time between part one and part two.
+/// Times are measured from the unlock of the puzzle.
+pub fn render_day(leaderboard: &Leaderboard, day: Day) -> String {
+    let unlock = unlock_time(leaderboard.year, day);
+
+    let mut rows: Vec<(&Member, Completion)> = leaderboard
+        .members
+        .iter()
+        .filter_map(|m| Some((m, *m.completions.get(&day)?)))
+        .collect();
+
+    // fastest second star first, then fastest first star.
+    rows.sort_by_key(|(_, c)| (c.part_2.unwrap_or(u64::MAX), c.part_1.unwrap_or(u64::MAX)));
+
+    let name_width = rows
+        .iter()
+        .map(|(m, _)| m.display_name().chars().count())
+        .max()
+        .unwrap_or(0)
+        .max(4);
+
+    let mut s = format!("{ANSI_BOLD}Day {day}{ANSI_RESET}\n");
+
+    if rows.is_empty() {
+        s.push_str("Nobody has solved this day yet.\n");
+        return s;
+    }
+
+    let _ = writeln!(
+        s,
+        "{ANSI_BOLD}{:<name_width$}  {:>8}  {:>8}  {:>8}{ANSI_RESET}",
+        "Name", "Part 1", "Part 2", "Delta"
+    );
+
+    let format_time = |ts: Option<u64>| {
+        ts.map_or_else(
+            || "-".into(),
+            |ts| format_seconds(ts.saturating_sub(unlock)),
+        )
+    };
+
+    for (member, completion) in rows {
+        let _ = writeln!(
+            s,
+            "{:<name_width$}  {:>8}  {:>8}  {:>8}",
+            member.display_name(),
+            format_time(completion.part_1),
+            format_time(completion.part_2),
+            completion
+                .delta()
+                .map_or_else(|| "-".into(), format_seconds)
+        );
+    }
+
+    s
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn json_u64(json: &HashMap<String, JsonValue>, key: &str) -> Option<u64> {
+    json.get(key)
+        .and_then(|v| v.get::<f64>())
+        .map(|x| *x as u64)
+}
+
+impl TryFrom<String> for Leaderboard {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected leaderboard to be a JSON object.")?;
+
+        let year = json
+            .get("event")
+            .and_then(|v| v.get::<String>())
+            .and_then(|event| Year::from_str(event).ok())
+            .ok_or("expected leaderboard.event to be a year.")?;
+
+        let owner_id =
+            json_u64(json, "owner_id").ok_or("expected leaderboard.owner_id to be a number.")?;
+
+        let mut members = json
+            .get("members")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected leaderboard.members to be an object.")?
+            .values()
+            .map(Member::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        members.sort_by(|a, b| {
+            b.local_score
+                .cmp(&a.local_score)
+                .then(b.stars.cmp(&a.stars))
+                .then(a.id.cmp(&b.id))
+        });
+
+        Ok(Leaderboard {
+            year,
+            owner_id,
+            members,
+        })
+    }
+}
+
+impl TryFrom<&JsonValue> for Member {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected member to be a JSON object.")?;
+
+        let id = json_u64(json, "id").ok_or("expected member.id to be a number.")?;
+
+        let name = json
+            .get("name")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("expected member.name to be null or string.")?;
+
+        let local_score =
+            json_u64(json, "local_score").ok_or("expected member.local_score to be a number.")?;
+        let stars = json_u64(json, "stars").ok_or("expected member.stars to be a number.")?;
+
+        let levels = json
+            .get("completion_day_level")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected member.completion_day_level to be an object.")?;
+
+        let mut completions = BTreeMap::new();
+
+        for (day, parts) in levels {
+            let day = Day::from_str(day).or(Err("expected completion days to be days."))?;
+            let parts = parts
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected completion day to be an object.")?;
+
+            let get_star_ts = |part: &str| {
+                parts
+                    .get(part)
+                    .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+                    .and_then(|star| json_u64(star, "get_star_ts"))
+            };
+
+            completions.insert(
+                day,
+                Completion {
+                    part_1: get_star_ts("1"),
+                    part_2: get_star_ts("2"),
+                },
+            );
+        }
+
+        Ok(Member {
+            id,
+            name: name.cloned(),
+            local_score,
+            stars,
+            completions,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_seconds, render_day, render_overview, unlock_time, Leaderboard};
+    use crate::{day, year};
+
+    const LEADERBOARD: &str = r#"{
+        "event": "2023",
+        "owner_id": 1,
+        "members": {
+            "1": {
+                "id": 1, "name": "Alice", "stars": 3, "local_score": 10, "global_score": 0, "last_star_ts": 1701495000,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1701407100, "star_index": 1 }, "2": { "get_star_ts": 1701407400, "star_index": 2 } },
+                    "2": { "1": { "get_star_ts": 1701495000, "star_index": 5 } }
+                }
+            },
+            "2": {
+                "id": 2, "name": null, "stars": 2, "local_score": 12, "global_score": 0, "last_star_ts": 1701407200,
+                "completion_day_level": {
+                    "1": { "1": { "get_star_ts": 1701407000, "star_index": 0 }, "2": { "get_star_ts": 1701407200, "star_index": 3 } }
+                }
+            },
+            "3": {
+                "id": 3, "name": "Carol", "stars": 0, "local_score": 0, "global_score": 0, "last_star_ts": 0,
+                "completion_day_level": {}
+            }
+        }
+    }"#;
+
+    fn get_leaderboard() -> Leaderboard {
+        Leaderboard::try_from(LEADERBOARD.to_string()).unwrap()
+    }
+
+    #[test]
+    fn parses_leaderboards() {
+        let leaderboard = get_leaderboard();
+        assert_eq!(leaderboard.year, year!(2023));
+        assert_eq!(leaderboard.owner_id, 1);
+        assert_eq!(leaderboard.last_day(), Some(day!(2)));
+
+        let ids: Vec<u64> = leaderboard.members.iter().map(|m| m.id).collect();
+        assert_eq!(ids, vec![2, 1, 3]);
+
+        let alice = &leaderboard.members[1];
+        assert_eq!(alice.completions[&day!(1)].delta(), Some(300));
+        assert_eq!(alice.completions[&day!(2)].part_2, None);
+        assert_eq!(leaderboard.members[0].display_name(), "(anonymous user #2)");
+    }
+
+    #[test]
+    fn computes_unlock_times() {
+        // 2023-12-01T05:00:00Z
+        assert_eq!(unlock_time(year!(2023), day!(1)), 1_701_406_800);
+        assert_eq!(unlock_time(year!(2015), day!(25)), 1_451_019_600);
+    }
+
+    #[test]
+    fn formats_seconds() {
+        assert_eq!(format_seconds(3_723), "01:02:03");
+        assert_eq!(format_seconds(86_400), ">24h");
+    }
+
+    #[test]
+    fn renders_tables() {
+        let leaderboard = get_leaderboard();
+
+        let overview = render_overview(&leaderboard);
+        assert!(overview.contains("  1) (anonymous user #2)     12   ★  ·\n"));
+        assert!(overview.contains("  2) Alice                   10   ★  ☆\n"));
+
+        let day = render_day(&leaderboard, day!(1));
+        let lines: Vec<&str> = day.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert_eq!(
+            lines[2],
+            "(anonymous user #2)  00:03:20  00:06:40  00:03:20"
+        );
+        assert_eq!(
+            lines[3],
+            "Alice                00:05:00  00:10:00  00:05:00"
+        );
+    }
+}
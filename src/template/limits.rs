@@ -0,0 +1,263 @@
+/// Wall-clock timeouts and an address-space limit for solutions.
+///
+/// The runner of multiple days passes limits to every solution through environment variables,
+/// which can also be set by hand, e.g. `AOC_DAY_TIMEOUT=30s cargo solve 16`. Solutions enforce them
+/// themselves: a watchdog thread reports a part that runs too long as timed out and exits the process.
+use std::{
+    env,
+    error::Error,
+    fmt::Display,
+    io::{self, Write},
+    process::{self, Command},
+    sync::{mpsc, Mutex, OnceLock},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::template::{
+    protocol::{self, PartResult},
+    stats::Stats,
+    PuzzleId,
+};
+
+pub static PART_TIMEOUT_ENV: &str = "AOC_PART_TIMEOUT";
+pub static DAY_TIMEOUT_ENV: &str = "AOC_DAY_TIMEOUT";
+pub static MEMORY_LIMIT_ENV: &str = "AOC_MEMORY_LIMIT";
+
+/// Limits for running a solution. Unset limits do not apply.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Limits {
+    /// Maximum time of the first run of a part. Benchmark samples are not included.
+    pub part_timeout: Option<Duration>,
+    /// Maximum time of all parts of a day, including benchmarks.
+    pub day_timeout: Option<Duration>,
+    /// Maximum address space of the solution process in bytes. Only enforced on unix.
+    pub memory_limit: Option<u64>,
+}
+
+impl Limits {
+    pub fn is_empty(&self) -> bool {
+        *self == Limits::default()
+    }
+
+    /// Reads limits from the environment. Invalid values are ignored with a warning.
+    pub fn from_env() -> Self {
+        fn read<T>(name: &str, parse: fn(&str) -> Result<T, LimitFromStrError>) -> Option<T> {
+            let value = env::var(name).ok()?;
+            parse(&value)
+                .map_err(|e| eprintln!("Ignoring {name}={value}: {e}"))
+                .ok()
+        }
+
+        Limits {
+            part_timeout: read(PART_TIMEOUT_ENV, parse_duration),
+            day_timeout: read(DAY_TIMEOUT_ENV, parse_duration),
+            memory_limit: read(MEMORY_LIMIT_ENV, parse_bytes),
+        }
+    }
+
+    /// Passes the limits to a child process.
+    pub fn apply_env(&self, cmd: &mut Command) {
+        if let Some(timeout) = self.part_timeout {
+            cmd.env(PART_TIMEOUT_ENV, format!("{}ms", timeout.as_millis()));
+        }
+        if let Some(timeout) = self.day_timeout {
+            cmd.env(DAY_TIMEOUT_ENV, format!("{}ms", timeout.as_millis()));
+        }
+        if let Some(bytes) = self.memory_limit {
+            cmd.env(MEMORY_LIMIT_ENV, bytes.to_string());
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct LimitFromStrError(&'static str);
+
+impl Error for LimitFromStrError {}
+
+impl Display for LimitFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.0)
+    }
+}
+
+/// Parses a duration like `500us`, `2ms`, `1.5s` or `2m`.
+pub fn parse_duration(s: &str) -> Result<Duration, LimitFromStrError> {
+    // longer suffixes first, `ms` also ends with `s`.
+    let units = [
+        ("ns", 1e-9),
+        ("us", 1e-6),
+        ("µs", 1e-6),
+        ("ms", 1e-3),
+        ("s", 1.0),
+        ("m", 60.0),
+    ];
+
+    units
+        .iter()
+        .find_map(|(suffix, factor)| {
+            let value: f64 = s.trim().strip_suffix(suffix)?.trim().parse().ok()?;
+            Duration::try_from_secs_f64(value * factor).ok()
+        })
+        .ok_or(LimitFromStrError(
+            "expecting a duration like `500us`, `2ms`, `1.5s` or `2m`",
+        ))
+}
+
+/// Parses a size in bytes like `4096`, `512K`, `256M` or `2G`. Units are powers of 1024.
+pub fn parse_bytes(s: &str) -> Result<u64, LimitFromStrError> {
+    let s = s.trim().trim_end_matches(['B', 'b']);
+
+    let (digits, factor) = match s.char_indices().last() {
+        Some((i, 'K' | 'k')) => (&s[..i], 1 << 10),
+        Some((i, 'M' | 'm')) => (&s[..i], 1 << 20),
+        Some((i, 'G' | 'g')) => (&s[..i], 1 << 30),
+        _ => (s, 1),
+    };
+
+    digits
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .and_then(|value| value.checked_mul(factor))
+        .filter(|bytes| *bytes > 0)
+        .ok_or(LimitFromStrError(
+            "expecting a size like `4096`, `512K`, `256M` or `2G`",
+        ))
+}
+
+fn limits() -> &'static Limits {
+    static LIMITS: OnceLock<Limits> = OnceLock::new();
+
+    LIMITS.get_or_init(|| {
+        let limits = Limits::from_env();
+        if let Some(bytes) = limits.memory_limit {
+            if let Err(e) = limit_address_space(bytes) {
+                eprintln!("Failed to apply memory limit: {e}");
+            }
+        }
+        limits
+    })
+}
+
+#[cfg(unix)]
+fn limit_address_space(bytes: u64) -> Result<(), io::Error> {
+    let limit = libc::rlimit {
+        rlim_cur: bytes as libc::rlim_t,
+        rlim_max: bytes as libc::rlim_t,
+    };
+
+    // SAFETY: `setrlimit` only reads the passed struct.
+    if unsafe { libc::setrlimit(libc::RLIMIT_AS, &limit) } == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(unix))]
+fn limit_address_space(_bytes: u64) -> Result<(), io::Error> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "memory limits are only supported on unix.",
+    ))
+}
+
+/// Stops watching a part when dropped.
+pub struct Watchdog {
+    _disarm: Option<mpsc::Sender<()>>,
+}
+
+/// Watches the first run of a part, which is limited by the part and the day timeout.
+pub fn watch_part(puzzle: PuzzleId, part: u8) -> Watchdog {
+    watch(puzzle, part, limits().part_timeout)
+}
+
+/// Watches the benchmark of a part, which is only limited by the day timeout.
+pub fn watch_bench(puzzle: PuzzleId, part: u8) -> Watchdog {
+    watch(puzzle, part, None)
+}
+
+fn watch(puzzle: PuzzleId, part: u8, part_timeout: Option<Duration>) -> Watchdog {
+    // the aggregate binary runs several days in one process, so track when the current day started.
+    static DAY_START: Mutex<Option<(PuzzleId, Instant)>> = Mutex::new(None);
+
+    let now = Instant::now();
+    let day_start = {
+        let mut day_start = DAY_START.lock().unwrap();
+        match *day_start {
+            Some((current, start)) if current == puzzle => start,
+            _ => day_start.insert((puzzle, now)).1,
+        }
+    };
+
+    let deadlines = [
+        limits().day_timeout.map(|timeout| day_start + timeout),
+        part_timeout.map(|timeout| now + timeout),
+    ];
+
+    let Some(deadline) = deadlines.into_iter().flatten().min() else {
+        return Watchdog { _disarm: None };
+    };
+
+    let (tx, rx) = mpsc::channel::<()>();
+
+    thread::spawn(move || {
+        // dropping the watchdog disconnects the channel and ends the wait.
+        if rx.recv_timeout(deadline.saturating_duration_since(Instant::now()))
+            == Err(mpsc::RecvTimeoutError::Timeout)
+        {
+            time_out(puzzle, part, now.elapsed());
+        }
+    });
+
+    Watchdog { _disarm: Some(tx) }
+}
+
+fn time_out(puzzle: PuzzleId, part: u8, elapsed: Duration) -> ! {
+    println!("\rPart {part}: timed out after {elapsed:.1?}             ");
+    let _ = io::stdout().flush();
+
+    let result = PartResult {
+        puzzle,
+        part,
+        answer: None,
+        error: None,
+        timed_out: true,
+        stats: Stats::default(),
+        memory: None,
+    };
+
+    if let Err(e) = protocol::emit(&result) {
+        eprintln!("failed to write result: {e}");
+    }
+
+    process::exit(1);
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{parse_bytes, parse_duration};
+
+    #[test]
+    fn parses_durations() {
+        assert_eq!(parse_duration("2ms").unwrap(), Duration::from_millis(2));
+        assert_eq!(parse_duration("1.5s").unwrap(), Duration::from_millis(1500));
+        assert_eq!(parse_duration("500µs").unwrap(), Duration::from_micros(500));
+        assert_eq!(parse_duration("2m").unwrap(), Duration::from_secs(120));
+        assert!(parse_duration("10").is_err());
+        assert!(parse_duration("-1s").is_err());
+    }
+
+    #[test]
+    fn parses_bytes() {
+        assert_eq!(parse_bytes("4096").unwrap(), 4096);
+        assert_eq!(parse_bytes("512K").unwrap(), 512 * 1024);
+        assert_eq!(parse_bytes("256MB").unwrap(), 256 * 1024 * 1024);
+        assert_eq!(parse_bytes("2g").unwrap(), 2 * 1024 * 1024 * 1024);
+        assert!(parse_bytes("lots").is_err());
+        assert!(parse_bytes("0M").is_err());
+    }
+}
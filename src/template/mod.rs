@@ -1,44 +1,96 @@
-use std::{env, fs};
+use std::{
+    env, fs,
+    io::{self, Read},
+    process,
+};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod examples;
+pub mod limits;
+pub mod registry;
 pub mod runner;
+pub mod visualize;
 
 pub use day::*;
+pub use limits::Limits;
+pub use regression::Tolerance;
+pub use year::*;
 
+mod answers;
 mod day;
+mod git;
+mod history;
+mod leaderboard;
+mod protocol;
 mod readme_benchmarks;
+mod readme_stars;
+mod regression;
 mod run_multi;
+mod stats;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that reads a text file to a string. E.g. like `data/2023/inputs/01.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn read_file(folder: &str, puzzle: PuzzleId) -> String {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
+    let filepath = cwd.join(puzzle.data_path(folder, "txt"));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
+/// Reads the input of a solution binary: the file passed with `--input <path>`, stdin for `--input -`
+/// and the puzzle input in `data/<year>/inputs` otherwise.
+#[must_use]
+pub fn read_input(puzzle: PuzzleId) -> String {
+    let args: Vec<String> = env::args().collect();
+
+    let Some(index) = args.iter().position(|x| x == "--input") else {
+        return read_file("inputs", puzzle);
+    };
+
+    let Some(path) = args.get(index + 1) else {
+        eprintln!("Unexpected command-line input. Format: cargo solve 1 --input <path|->");
+        process::exit(1);
+    };
+
+    let input = if path == "-" {
+        let mut input = String::new();
+        io::stdin().read_to_string(&mut input).map(|_| input)
+    } else {
+        fs::read_to_string(path)
+    };
+
+    input.unwrap_or_else(|e| {
+        eprintln!("could not read input from \"{path}\": {e}");
+        process::exit(1);
+    })
+}
+
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, puzzle: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
         .join("data")
+        .join(puzzle.year.to_string())
         .join(folder)
-        .join(format!("{day}-{part}.txt"));
+        .join(format!("{}-{part}.txt", puzzle.day));
     let f = fs::read_to_string(filepath);
     f.expect("could not open input file")
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `DAY`, `YEAR` and `PUZZLE` and sets up the input and runner for each part.
 ///
+/// The year is read from the name of the solution binary (e.g. `2023-01`).
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+/// Parts return an `Option` or a `Result`, see [`runner::PartOutput`].
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
@@ -54,15 +106,32 @@ macro_rules! solution {
     (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
+        /// The year of the current puzzle.
+        const YEAR: $crate::template::Year =
+            $crate::template::Year::__from_module_path(module_path!());
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new(YEAR, DAY);
 
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
-        fn main() {
+        /// Registers this solution with the aggregate `solutions` binary.
+        pub const SOLUTION: $crate::template::registry::Solution =
+            $crate::template::registry::Solution {
+                puzzle: PUZZLE,
+                run: run_parts,
+            };
+
+        fn run_parts(input: &str) {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $( run_part($func, input, PUZZLE, $part); )*
+        }
+
+        #[allow(dead_code)]
+        fn main() {
+            let input = $crate::template::read_input(PUZZLE);
+            run_parts(&input);
         }
     };
 }
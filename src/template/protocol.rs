@@ -0,0 +1,276 @@
+/// Machine-readable protocol between solution binaries and the runner of multiple days.
+///
+/// When the environment variable `AOC_RESULT_FILE` is set, every solution part appends
+/// one JSON object per line to that file. This avoids scraping the human-readable output,
+/// which breaks for multi-line answers or answers that resemble the timing format.
+use std::{
+    collections::HashMap,
+    env,
+    fs::{self, OpenOptions},
+    io::{self, Write},
+    path::Path,
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{
+    stats::{MemoryStats, Stats},
+    Day, PuzzleId, Year,
+};
+
+pub static RESULT_FILE_ENV: &str = "AOC_RESULT_FILE";
+
+/// The outcome of running a single solution part.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub puzzle: PuzzleId,
+    pub part: u8,
+    pub answer: Option<String>,
+    pub error: Option<String>,
+    /// The part was stopped by the watchdog of `limits`. Errors returned by the part do not count.
+    pub timed_out: bool,
+    pub stats: Stats,
+    /// Heap usage of the first run, only measured in builds with the `dhat-heap` feature.
+    pub memory: Option<MemoryStats>,
+}
+
+impl PartResult {
+    /// A part without answer and error has not been implemented yet.
+    pub fn is_solved(&self) -> bool {
+        self.answer.is_some()
+    }
+
+    /// The part was stopped because it exceeded a timeout.
+    pub fn is_timed_out(&self) -> bool {
+        self.timed_out
+    }
+
+    /// The part returned an error.
+    pub fn is_failed(&self) -> bool {
+        self.error.is_some() && !self.is_timed_out()
+    }
+}
+
+/// Appends a result to the file named by `AOC_RESULT_FILE`, if set.
+pub fn emit(result: &PartResult) -> Result<(), io::Error> {
+    let Ok(path) = env::var(RESULT_FILE_ENV) else {
+        return Ok(());
+    };
+
+    let line = JsonValue::from(result)
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    writeln!(file, "{line}")
+}
+
+/// Reads all results from a result file. A missing file yields no results.
+pub fn read(path: &Path) -> Result<Vec<PartResult>, String> {
+    let Ok(contents) = fs::read_to_string(path) else {
+        return Ok(vec![]);
+    };
+
+    contents
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(PartResult::from_str)
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn optional_string(value: Option<&String>) -> JsonValue {
+    value.map_or(JsonValue::Null, |x| JsonValue::String(x.clone()))
+}
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "year".into(),
+            JsonValue::Number(value.puzzle.year.into_inner().into()),
+        );
+        map.insert(
+            "day".into(),
+            JsonValue::Number(value.puzzle.day.into_inner().into()),
+        );
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), optional_string(value.answer.as_ref()));
+        map.insert("error".into(), optional_string(value.error.as_ref()));
+        map.insert("timed_out".into(), JsonValue::Boolean(value.timed_out));
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "nanos".into(),
+                JsonValue::Number(value.stats.median.as_nanos() as f64),
+            );
+            map.insert(
+                "samples".into(),
+                JsonValue::Number(value.stats.samples as f64),
+            );
+        }
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+        map.insert(
+            "memory".into(),
+            value
+                .memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl FromStr for PartResult {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let json = JsonValue::from_str(s).or(Err("result is not valid JSON."))?;
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected result to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected result.{key} to be a number."))
+        };
+
+        let optional_string = |key: &str| {
+            json.get(key)
+                .map(|v| if v.is_null() { None } else { v.get::<String>() })
+                .ok_or(format!("Expected result.{key} to be null or string."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let year = Year::new(number("year")? as u16).ok_or("Expected result.year to be a Year.")?;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let day = Day::new(number("day")? as u8).ok_or("Expected result.day to be a Day.")?;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let part = number("part")? as u8;
+
+        let stats = Stats::try_from(
+            json.get("stats")
+                .ok_or("Expected result.stats to be set.")?,
+        )?;
+
+        let timed_out = json
+            .get("timed_out")
+            .and_then(|v| v.get::<bool>().copied())
+            .ok_or("Expected result.timed_out to be a boolean.")?;
+
+        let memory = match json.get("memory") {
+            Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+            _ => None,
+        };
+
+        Ok(PartResult {
+            puzzle: PuzzleId::new(year, day),
+            part,
+            answer: optional_string("answer")?.cloned(),
+            error: optional_string("error")?.cloned(),
+            timed_out,
+            stats,
+            memory,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{str::FromStr, time::Duration};
+
+    use tinyjson::JsonValue;
+
+    use super::PartResult;
+    use crate::{
+        day,
+        template::stats::{MemoryStats, Stats},
+        template::PuzzleId,
+        year,
+    };
+
+    fn get_mock_result(answer: Option<&str>) -> PartResult {
+        PartResult {
+            puzzle: PuzzleId::new(year!(2023), day!(7)),
+            part: 2,
+            answer: answer.map(String::from),
+            error: None,
+            timed_out: false,
+            stats: Stats::from_samples(&[Duration::from_nanos(1500)]),
+            memory: None,
+        }
+    }
+
+    #[test]
+    fn round_trips_results() {
+        let result = get_mock_result(Some("42"));
+        let line = JsonValue::from(&result).stringify().unwrap();
+        assert_eq!(PartResult::from_str(&line).unwrap(), result);
+    }
+
+    #[test]
+    fn round_trips_memory() {
+        let result = PartResult {
+            memory: Some(MemoryStats {
+                total_blocks: 3,
+                total_bytes: 276,
+                max_bytes: 232,
+            }),
+            ..get_mock_result(Some("42"))
+        };
+        let line = JsonValue::from(&result).stringify().unwrap();
+        assert_eq!(PartResult::from_str(&line).unwrap(), result);
+    }
+
+    #[test]
+    fn round_trips_answers_resembling_output() {
+        let result = get_mock_result(Some("@ @ (2s @ 5 samples)\nline two"));
+        let line = JsonValue::from(&result).stringify().unwrap();
+        assert_eq!(line.contains('\n'), false);
+        assert_eq!(PartResult::from_str(&line).unwrap(), result);
+    }
+
+    #[test]
+    fn parses_unsolved_results() {
+        let result = get_mock_result(None);
+        let line = JsonValue::from(&result).stringify().unwrap();
+        let parsed = PartResult::from_str(&line).unwrap();
+        assert_eq!(parsed.is_solved(), false);
+        assert_eq!(parsed.error, None);
+    }
+
+    #[test]
+    fn distinguishes_timeouts_from_errors() {
+        let failed = PartResult {
+            error: Some("timed out".into()),
+            ..get_mock_result(None)
+        };
+        let line = JsonValue::from(&failed).stringify().unwrap();
+        let parsed = PartResult::from_str(&line).unwrap();
+        assert_eq!(parsed.is_failed(), true);
+        assert_eq!(parsed.is_timed_out(), false);
+
+        let timed_out = PartResult {
+            timed_out: true,
+            ..get_mock_result(None)
+        };
+        let line = JsonValue::from(&timed_out).stringify().unwrap();
+        let parsed = PartResult::from_str(&line).unwrap();
+        assert_eq!(parsed.is_failed(), false);
+        assert_eq!(parsed.is_timed_out(), true);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_results() {
+        PartResult::from_str(r#"{ "year": 2023, "day": 26, "part": 1 }"#).unwrap();
+    }
+}
@@ -3,9 +3,11 @@
 use std::{fs, io};
 
 use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::{PuzzleId, Year};
 
-static MARKER: &str = "<!--- benchmarking table --->";
+fn get_marker(year: Year) -> String {
+    format!("<!--- benchmarking table {year} --->")
+}
 
 #[derive(Debug)]
 pub enum Error {
@@ -25,12 +27,12 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./{}", puzzle.bin_path())
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+pub(crate) fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -51,55 +53,119 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
+fn construct_table(prefix: &str, year: Year, timings: Timings, total_millis: f64) -> String {
+    let header = format!("{prefix} {year} Benchmarks");
+    let marker = get_marker(year);
+
+    // heap usage is only shown once it was measured with `cargo time --memory`.
+    let has_memory = timings
+        .data
+        .iter()
+        .any(|t| t.part_1_memory.is_some() || t.part_2_memory.is_some());
 
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
+    let mut lines: Vec<String> = vec![marker.clone(), header, String::new()];
+
+    if has_memory {
+        lines.push("| Day | Part 1 | Part 2 | Part 1 Memory | Part 2 Memory |".into());
+        lines.push("| :---: | :---: | :---: | :---: | :---: |".into());
+    } else {
+        lines.push("| Day | Part 1 | Part 2 |".into());
+        lines.push("| :---: | :---: | :---:  |".into());
+    }
 
     for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
+        let path = get_path_for_bin(PuzzleId::new(year, timing.day));
+        let format_part = |part: u8, time: Option<String>| {
+            time.unwrap_or_else(|| {
+                if timing.timed_out == Some(part) {
+                    "timed out".into()
+                } else if timing.failed.contains(&part) {
+                    "failed".into()
+                } else {
+                    "-".into()
+                }
+            })
+        };
+
+        let mut line = format!(
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            format_part(1, timing.part_1.clone()),
+            format_part(2, timing.part_2.clone())
+        );
+
+        if has_memory {
+            for part in [1, 2] {
+                let memory = timing
+                    .part_memory(part)
+                    .map_or_else(|| "-".into(), ToString::to_string);
+                line += &format!(" `{memory}` |");
+            }
+        }
+
+        lines.push(line);
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    lines.push(marker);
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
-    s.replace_range(positions.pos_start..positions.pos_end, &table);
+fn update_content(
+    s: &mut String,
+    year: Year,
+    timings: Timings,
+    total_millis: f64,
+) -> Result<(), Error> {
+    let table = construct_table("##", year, timings, total_millis);
+    replace_table(s, &get_marker(year), &table)
+}
+
+/// Replaces the table between the two occurrences of `marker`, including the markers themselves.
+pub(crate) fn replace_table(s: &mut String, marker: &str, table: &str) -> Result<(), Error> {
+    let positions = locate_table(s, marker)?;
+    s.replace_range(positions.pos_start..positions.pos_end, table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
+/// Writes a table to the readme, appending it if the readme does not contain `marker` yet.
+/// `update_content` replaces the table in the contents of the readme.
+pub(crate) fn update_readme(
+    marker: &str,
+    update_content: impl FnOnce(&mut String) -> Result<(), Error>,
+) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
-    let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+
+    if !readme.contains(marker) {
+        readme.push_str(&format!("\n{marker}\n{marker}\n"));
+    }
+
+    update_content(&mut readme)?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
+/// Updates the benchmark table of a year, appending a new table if the year has none yet.
+pub fn update(year: Year, timings: Timings) -> Result<(), Error> {
+    let total_millis = timings.total_millis();
+    update_readme(&get_marker(year), |readme| {
+        update_content(readme, year, timings, total_millis)
+    })
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{get_marker, update_content};
+    use crate::{
+        day,
+        template::stats::MemoryStats,
+        template::timings::{Timing, Timings},
+        year,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
@@ -108,19 +174,37 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 3e+10,
+                    timed_out: None,
+                    failed: vec![],
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 7e+10,
+                    timed_out: None,
+                    failed: vec![],
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 9e+10,
+                    timed_out: None,
+                    failed: vec![],
                 },
             ],
         }
@@ -130,53 +214,91 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
-        let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let marker = get_marker(year!(2023));
+        let mut s = format!("{} {} {}", marker, marker, marker);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        let marker = get_marker(year!(2023));
+        let mut s = format!("foo\nbar\n{}{}\nbaz", marker, marker);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.contains("## 2023 Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
-        let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
-        assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
+        let marker = get_marker(year!(2023));
+        let mut s = format!("foo\nbar\n{}{}\nbaz", marker, marker);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches(&marker).collect::<Vec<&str>>().len(), 2);
+        assert_eq!(
+            s.matches("## 2023 Benchmarks").collect::<Vec<&str>>().len(),
+            1
+        );
+    }
+
+    #[test]
+    fn keeps_tables_of_other_years() {
+        let (marker_2022, marker_2023) = (get_marker(year!(2022)), get_marker(year!(2023)));
+        let mut s = format!("{marker_2022}{marker_2022}\n{marker_2023}{marker_2023}");
+        update_content(&mut s, year!(2022), get_mock_timings(), 190.0).unwrap();
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
+        assert_eq!(s.matches("## 2022 Benchmarks").count(), 1);
+        assert_eq!(s.matches("## 2023 Benchmarks").count(), 1);
+        assert_eq!(s.matches("./src/bin/2022-01.rs").count(), 1);
     }
 
     #[test]
     fn format_benchmarks() {
-        let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        let marker = get_marker(year!(2023));
+        let mut s = format!("foo\nbar\n{}\n{}\nbaz", marker, marker);
+        update_content(&mut s, year!(2023), get_mock_timings(), 190.0).unwrap();
         let expected = [
             "foo",
             "bar",
-            "<!--- benchmarking table --->",
-            "## Benchmarks",
+            "<!--- benchmarking table 2023 --->",
+            "## 2023 Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
-            "<!--- benchmarking table --->",
+            "<!--- benchmarking table 2023 --->",
             "baz",
         ]
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_memory_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_1_memory = Some(MemoryStats {
+            total_blocks: 3,
+            total_bytes: 276,
+            max_bytes: 232,
+        });
+
+        let marker = get_marker(year!(2023));
+        let mut s = format!("{marker}\n{marker}");
+        update_content(&mut s, year!(2023), timings, 190.0).unwrap();
+
+        assert!(s.contains("| Day | Part 1 | Part 2 | Part 1 Memory | Part 2 Memory |"));
+        assert!(s.contains(
+            "| [Day 1](./src/bin/2023-01.rs) | `10ms` | `20ms` | `232 B peak, 276 B in 3 allocations` | `-` |"
+        ));
+        assert!(s.contains("| [Day 2](./src/bin/2023-02.rs) | `30ms` | `40ms` | `-` | `-` |"));
+    }
 }
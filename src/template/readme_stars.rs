@@ -0,0 +1,235 @@
+/// Module that updates the readme with the stars of a year.
+/// Produces the same table as the `advent-readme-stars` workflow, but from locally recorded progress
+/// instead of the advent of code API.
+use std::collections::BTreeMap;
+
+use crate::template::answers::Answers;
+use crate::template::aoc_client::{self, SubmissionOutcome};
+use crate::template::readme_benchmarks::{replace_table, update_readme, Error};
+use crate::template::submissions::Submissions;
+use crate::template::{Day, PuzzleId, Year};
+
+/// Scoped to the year like the benchmark table, so tables of several years can live in one readme.
+/// The `advent-readme-stars` workflow is configured to use the same marker, so that either can maintain the table.
+fn get_marker(year: Year) -> String {
+    format!("<!--- advent_readme_stars table {year} --->")
+}
+
+/// Solved parts of a day.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stars {
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+/// Collects the stars of a year from accepted submissions and recorded answers.
+pub fn collect_stars(submissions: &Submissions, answers: &Answers) -> BTreeMap<Day, Stars> {
+    let mut stars: BTreeMap<Day, Stars> = BTreeMap::new();
+
+    let mut add_star = |day: Day, part: u8| {
+        let stars = stars.entry(day).or_default();
+        match part {
+            1 => stars.part_1 = true,
+            2 => stars.part_2 = true,
+            _ => {}
+        }
+    };
+
+    for submission in &submissions.data {
+        if submission.outcome == SubmissionOutcome::Correct {
+            add_star(submission.day, submission.part);
+        }
+    }
+
+    for answer in &answers.data {
+        for part in [1, 2] {
+            if answer.get(part).is_some() {
+                add_star(answer.day, part);
+            }
+        }
+    }
+
+    stars
+}
+
+fn construct_table(
+    year: Year,
+    stars: &BTreeMap<Day, Stars>,
+    get_title: impl Fn(Day) -> Option<String>,
+) -> String {
+    let marker = get_marker(year);
+    let mut lines: Vec<String> = vec![
+        marker.clone(),
+        format!("## {year} Results"),
+        String::new(),
+        "| Day | Part 1 | Part 2 |".into(),
+        "| :---: | :---: | :---: |".into(),
+    ];
+
+    for (day, stars) in stars {
+        let day_number = day.into_inner();
+        let name = match get_title(*day) {
+            // pipes would end the table cell.
+            Some(title) => format!("Day {day_number}: {}", title.replace('|', "\\|")),
+            None => format!("Day {day_number}"),
+        };
+        let star = |solved: bool| if solved { "⭐" } else { " " };
+
+        lines.push(format!(
+            "| [{name}](https://adventofcode.com/{year}/day/{day_number}) | {} | {} |",
+            star(stars.part_1),
+            star(stars.part_2)
+        ));
+    }
+
+    lines.push(marker);
+    lines.join("\n")
+}
+
+fn update_content(
+    s: &mut String,
+    year: Year,
+    stars: &BTreeMap<Day, Stars>,
+    get_title: impl Fn(Day) -> Option<String>,
+) -> Result<(), Error> {
+    replace_table(
+        s,
+        &get_marker(year),
+        &construct_table(year, stars, get_title),
+    )
+}
+
+/// Updates the stars table with the progress of a year, appending a new table if the readme has none yet.
+/// Titles are read from the stored puzzle descriptions.
+/// Returns `false` without touching the readme if no progress was recorded, e.g. because the table is
+/// maintained by the workflow.
+pub fn update(year: Year) -> Result<bool, Error> {
+    let stars = collect_stars(
+        &Submissions::read_from_file(year),
+        &Answers::read_from_file(year),
+    );
+
+    if stars.is_empty() {
+        return Ok(false);
+    }
+
+    update_readme(&get_marker(year), |readme| {
+        update_content(readme, year, &stars, |day| {
+            aoc_client::read_title(PuzzleId::new(year, day))
+        })
+    })?;
+
+    Ok(true)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::{collect_stars, get_marker, update_content, Stars};
+    use crate::{
+        day,
+        template::{
+            answers::{Answer, Answers},
+            aoc_client::SubmissionOutcome,
+            submissions::{Submission, Submissions},
+            Day,
+        },
+        year,
+    };
+
+    fn get_mock_submission(day: Day, part: u8, outcome: SubmissionOutcome) -> Submission {
+        Submission {
+            day,
+            part,
+            answer: "42".into(),
+            outcome,
+            timestamp: 0,
+        }
+    }
+
+    #[test]
+    fn collects_stars() {
+        let submissions = Submissions {
+            data: vec![
+                get_mock_submission(day!(1), 1, SubmissionOutcome::TooLow),
+                get_mock_submission(day!(1), 1, SubmissionOutcome::Correct),
+                get_mock_submission(day!(2), 2, SubmissionOutcome::Incorrect),
+            ],
+        };
+        let answers = Answers {
+            data: vec![Answer {
+                day: day!(3),
+                part_1: Some("1".into()),
+                part_2: Some("2".into()),
+            }],
+        };
+
+        let stars = collect_stars(&submissions, &answers);
+        assert_eq!(
+            stars.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    day!(1),
+                    Stars {
+                        part_1: true,
+                        part_2: false
+                    }
+                ),
+                (
+                    day!(3),
+                    Stars {
+                        part_1: true,
+                        part_2: true
+                    }
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_stars() {
+        let stars = BTreeMap::from([
+            (
+                day!(1),
+                Stars {
+                    part_1: true,
+                    part_2: true,
+                },
+            ),
+            (
+                day!(2),
+                Stars {
+                    part_1: true,
+                    part_2: false,
+                },
+            ),
+        ]);
+
+        let (marker, other_marker) = (get_marker(year!(2022)), get_marker(year!(2023)));
+        let mut s = format!(
+            "foo\n{marker}\n## 2022 Results\n{marker}\n{other_marker}\n## 2023 Results\n{other_marker}"
+        );
+        update_content(&mut s, year!(2022), &stars, |day| {
+            (day == day!(1)).then(|| "Not Quite Lisp".to_string())
+        })
+        .unwrap();
+
+        let expected = [
+            "foo",
+            &marker,
+            "## 2022 Results",
+            "",
+            "| Day | Part 1 | Part 2 |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1: Not Quite Lisp](https://adventofcode.com/2022/day/1) | ⭐ | ⭐ |",
+            "| [Day 2](https://adventofcode.com/2022/day/2) | ⭐ |   |",
+            &marker,
+            &other_marker,
+            "## 2023 Results",
+            &other_marker,
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+}
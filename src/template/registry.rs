@@ -0,0 +1,64 @@
+/// Registry of solutions that run in a single process.
+///
+/// Every `solution!` registers a [`Solution`]. A build script collects the solutions in `src/bin`
+/// into the aggregate `solutions` binary, which runs many days without invoking cargo per day.
+use std::{collections::HashSet, fs, panic};
+
+use crate::template::{all_days, run_multi::print_header, Day, PuzzleId, Year};
+
+/// A solution registered by the `solution!` macro.
+#[derive(Clone, Copy)]
+pub struct Solution {
+    pub puzzle: PuzzleId,
+    /// Runs all implemented parts against an input through `runner::run_part`.
+    pub run: fn(&str),
+}
+
+/// Finds the registered solution of a puzzle.
+pub fn find(solutions: &[Solution], puzzle: PuzzleId) -> Option<&Solution> {
+    solutions.iter().find(|s| s.puzzle == puzzle)
+}
+
+/// Entry point of the aggregate binary.
+///
+/// Accepts `--year <year>` and `--days <day,day,...>`, which default to `AOC_YEAR` and all days.
+/// Other flags like `--time` are picked up by `runner::run_part` as usual.
+pub fn run(solutions: &[Solution]) {
+    let mut args = pico_args::Arguments::from_env();
+
+    let year = args
+        .opt_value_from_str("--year")
+        .ok()
+        .flatten()
+        .or_else(Year::from_env)
+        .expect("expected `--year` or the AOC_YEAR environment variable to be set.");
+
+    let days: HashSet<Day> = match args.opt_value_from_str::<_, String>("--days") {
+        Ok(Some(days)) => days.split(',').filter_map(|d| d.parse().ok()).collect(),
+        _ => all_days().collect(),
+    };
+
+    let days = all_days().filter(|day| days.contains(day));
+
+    for (index, day) in days.enumerate() {
+        print_header(day, index > 0);
+
+        let puzzle = PuzzleId::new(year, day);
+
+        let Some(solution) = find(solutions, puzzle) else {
+            println!("Not solved.");
+            continue;
+        };
+
+        let Ok(input) = fs::read_to_string(puzzle.data_path("inputs", "txt")) else {
+            eprintln!("could not open input file for {puzzle}.");
+            println!("Not solved.");
+            continue;
+        };
+
+        // a panicking day should not take the remaining days down with it.
+        if panic::catch_unwind(|| (solution.run)(&input)).is_err() {
+            println!("Not solved.");
+        }
+    }
+}
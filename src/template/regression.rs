@@ -0,0 +1,283 @@
+/// Detects performance regressions of a benchmark run compared to stored timings.
+use std::{error::Error, fmt::Display, str::FromStr, time::Duration};
+
+use crate::template::{limits::parse_duration, timings::Timings, Day};
+
+/// How much slower a part or day may get before it counts as a regression.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Tolerance {
+    /// Percentage of the stored time, e.g. `10%`.
+    Relative(f64),
+    /// Fixed amount of time, e.g. `500µs`.
+    Absolute(Duration),
+}
+
+impl Default for Tolerance {
+    fn default() -> Self {
+        Tolerance::Relative(10.0)
+    }
+}
+
+impl Tolerance {
+    fn is_exceeded(self, stored_nanos: f64, current_nanos: f64) -> bool {
+        let slowdown = current_nanos - stored_nanos;
+        match self {
+            Tolerance::Relative(percent) => slowdown > stored_nanos * percent / 100.0,
+            Tolerance::Absolute(duration) => slowdown > duration.as_secs_f64() * 1e9,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct ToleranceFromStrError;
+
+impl Error for ToleranceFromStrError {}
+
+impl Display for ToleranceFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a percentage like `10%` or a duration like `500us`, `2ms` or `1s`")
+    }
+}
+
+impl FromStr for Tolerance {
+    type Err = ToleranceFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+
+        if let Some(percent) = s.strip_suffix('%') {
+            let percent: f64 = percent.trim().parse().map_err(|_| ToleranceFromStrError)?;
+            return if percent >= 0.0 {
+                Ok(Tolerance::Relative(percent))
+            } else {
+                Err(ToleranceFromStrError)
+            };
+        }
+
+        parse_duration(s)
+            .map(Tolerance::Absolute)
+            .map_err(|_| ToleranceFromStrError)
+    }
+}
+
+/// The current result of a regressed part or day.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Outcome {
+    /// Finished, but slower than tolerated.
+    Slower(f64),
+    TimedOut,
+    Failed,
+    /// Returned no answer.
+    Unsolved,
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+            Outcome::Slower(nanos) => write!(f, "{:.1?}", Duration::from_nanos(*nanos as u64)),
+            Outcome::TimedOut => write!(f, "timed out"),
+            Outcome::Failed => write!(f, "failed"),
+            Outcome::Unsolved => write!(f, "no answer"),
+        }
+    }
+}
+
+/// A part, or the total of a day when `part` is `None`, that got slower than tolerated or stopped finishing.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Regression {
+    pub day: Day,
+    pub part: Option<u8>,
+    pub stored_nanos: f64,
+    pub current: Outcome,
+}
+
+impl Regression {
+    /// Relative slowdown in percent, if the part still finishes.
+    pub fn percent(&self) -> Option<f64> {
+        match self.current {
+            Outcome::Slower(nanos) => Some((nanos - self.stored_nanos) / self.stored_nanos * 100.0),
+            _ => None,
+        }
+    }
+}
+
+/// Finds days and parts of `current` that are slower than in `stored` by more than `tolerance`.
+/// Parts are compared by their median, days by their total. Parts that were benchmarked in `stored` but
+/// timed out, failed or returned no answer in `current` are regressions as well. Days missing from either set are skipped.
+pub fn find_regressions(
+    stored: &Timings,
+    current: &Timings,
+    tolerance: Tolerance,
+) -> Vec<Regression> {
+    let mut regressions = vec![];
+
+    for new in &current.data {
+        let Some(old) = stored.data.iter().find(|t| t.day == new.day) else {
+            continue;
+        };
+
+        #[allow(clippy::cast_precision_loss)]
+        for part in [1, 2] {
+            let Some(old_stats) = old.part_stats(part) else {
+                continue;
+            };
+            let stored_nanos = old_stats.median.as_nanos() as f64;
+
+            let current = match new.part_stats(part) {
+                Some(new_stats) => {
+                    let current_nanos = new_stats.median.as_nanos() as f64;
+                    if !tolerance.is_exceeded(stored_nanos, current_nanos) {
+                        continue;
+                    }
+                    Outcome::Slower(current_nanos)
+                }
+                // a timeout stops the day, so later parts do not run either.
+                None if new.timed_out.is_some_and(|timed_out| timed_out <= part) => {
+                    Outcome::TimedOut
+                }
+                None if new.failed.contains(&part) => Outcome::Failed,
+                None => Outcome::Unsolved,
+            };
+
+            regressions.push(Regression {
+                day: new.day,
+                part: Some(part),
+                stored_nanos,
+                current,
+            });
+        }
+
+        // totals are only comparable if the same parts were benchmarked.
+        let same_parts = old.part_1.is_some() == new.part_1.is_some()
+            && old.part_2.is_some() == new.part_2.is_some();
+
+        if same_parts
+            && old.total_nanos > 0.0
+            && tolerance.is_exceeded(old.total_nanos, new.total_nanos)
+        {
+            regressions.push(Regression {
+                day: new.day,
+                part: None,
+                stored_nanos: old.total_nanos,
+                current: Outcome::Slower(new.total_nanos),
+            });
+        }
+    }
+
+    regressions
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{str::FromStr, time::Duration};
+
+    use super::{find_regressions, Outcome, Tolerance};
+    use crate::{
+        day,
+        template::{
+            stats::Stats,
+            timings::{Timing, Timings},
+        },
+    };
+
+    fn get_mock_timings(part_1_nanos: u64, part_2_nanos: u64) -> Timings {
+        let stats = |nanos| Some(Stats::from_samples(&[Duration::from_nanos(nanos)]));
+        #[allow(clippy::cast_precision_loss)]
+        Timings {
+            data: vec![Timing {
+                day: day!(1),
+                part_1: Some(format!("{part_1_nanos}ns")),
+                part_2: Some(format!("{part_2_nanos}ns")),
+                part_1_stats: stats(part_1_nanos),
+                part_2_stats: stats(part_2_nanos),
+                part_1_memory: None,
+                part_2_memory: None,
+                total_nanos: (part_1_nanos + part_2_nanos) as f64,
+                timed_out: None,
+                failed: vec![],
+            }],
+        }
+    }
+
+    #[test]
+    fn parses_tolerances() {
+        assert_eq!(Tolerance::from_str("5%").unwrap(), Tolerance::Relative(5.0));
+        assert_eq!(
+            Tolerance::from_str("2ms").unwrap(),
+            Tolerance::Absolute(Duration::from_millis(2))
+        );
+        assert_eq!(
+            Tolerance::from_str("1.5s").unwrap(),
+            Tolerance::Absolute(Duration::from_millis(1500))
+        );
+        assert_eq!(
+            Tolerance::from_str("500µs").unwrap(),
+            Tolerance::Absolute(Duration::from_micros(500))
+        );
+        assert!(Tolerance::from_str("fast").is_err());
+        assert!(Tolerance::from_str("-5%").is_err());
+    }
+
+    #[test]
+    fn finds_relative_regressions() {
+        let stored = get_mock_timings(1000, 1000);
+        let current = get_mock_timings(1050, 1200);
+        let regressions = find_regressions(&stored, &current, Tolerance::Relative(10.0));
+        assert_eq!(regressions.len(), 2);
+        assert_eq!(regressions[0].part, Some(2));
+        assert_eq!(regressions[0].percent(), Some(20.0));
+        assert_eq!(regressions[1].part, None);
+        assert_eq!(regressions[1].current, Outcome::Slower(2250.0));
+    }
+
+    #[test]
+    fn finds_absolute_regressions() {
+        let stored = get_mock_timings(1000, 1000);
+        let current = get_mock_timings(1400, 1600);
+        let regressions = find_regressions(
+            &stored,
+            &current,
+            Tolerance::Absolute(Duration::from_nanos(500)),
+        );
+        assert_eq!(regressions.len(), 2);
+        assert_eq!(regressions[0].part, Some(2));
+        assert_eq!(regressions[1].part, None);
+    }
+
+    #[test]
+    fn ignores_speedups() {
+        let stored = get_mock_timings(1000, 1000);
+        let current = get_mock_timings(10, 10);
+        assert!(find_regressions(&stored, &current, Tolerance::default()).is_empty());
+    }
+
+    #[test]
+    fn finds_parts_that_stopped_finishing() {
+        let stored = get_mock_timings(1000, 1000);
+        let mut current = get_mock_timings(1000, 1000);
+        let timing = &mut current.data[0];
+        (timing.part_1, timing.part_1_stats) = (None, None);
+        (timing.part_2, timing.part_2_stats) = (None, None);
+        timing.failed = vec![1];
+
+        let outcomes = |current: &Timings| {
+            find_regressions(&stored, current, Tolerance::default())
+                .iter()
+                .map(|r| (r.part, r.current))
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            outcomes(&current),
+            vec![(Some(1), Outcome::Failed), (Some(2), Outcome::Unsolved)]
+        );
+
+        current.data[0].failed = vec![];
+        current.data[0].timed_out = Some(1);
+        assert_eq!(
+            outcomes(&current),
+            vec![(Some(1), Outcome::TimedOut), (Some(2), Outcome::TimedOut)]
+        );
+    }
+}
@@ -1,38 +1,104 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, HashSet},
+    fmt::{self, Display},
+    io::{self, Write},
+    sync::{mpsc, Mutex},
+    thread,
+};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{Day, Limits, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
     all_days,
+    protocol::PartResult,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
-
+/// Runs the solutions of several days.
+/// Sequential runs invoke the aggregate `solutions` binary once, which runs every day in-process.
+/// With `jobs > 1`, days run concurrently and their output is buffered and printed in day order.
+/// Timed runs are always sequential so that benchmarks do not compete for the CPU.
+/// With `limits`, every day runs in its own process, so that a day stopped by a limit does not stop the others.
+/// With `input`, days read their input from that path instead of the puzzle input, see [`read_input`](crate::template::read_input).
+pub fn run_multi(
+    year: Year,
+    days_to_run: &HashSet<Day>,
+    is_release: bool,
+    is_timed: bool,
+    jobs: usize,
+    limits: Limits,
+    input: Option<&str>,
+) -> Option<Timings> {
     // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+    let days: Vec<Day> = all_days().filter(|day| days_to_run.contains(day)).collect();
+
+    let mut timings: Vec<Timing> = Vec::with_capacity(days.len());
+
+    let jobs = if is_timed { 1 } else { jobs.max(1) };
+
+    if jobs == 1 && limits.is_empty() && input.is_none() {
+        // the aggregate binary prints day headers itself.
+        let results = child_commands::run_registry(
+            year,
+            &days,
+            is_timed,
+            is_release,
+            &mut io::stdout(),
+            &mut io::stderr(),
+        )
+        .unwrap_or_else(|e| {
+            eprintln!("failed to run solutions: {e}");
+            vec![]
+        });
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+        for &day in &days {
+            let results: Vec<PartResult> = results
+                .iter()
+                .filter(|r| r.puzzle.day == day)
+                .cloned()
+                .collect();
+            if !results.is_empty() {
+                timings.push(child_commands::parse_results(&results, day));
+            }
+        }
+    } else if jobs == 1 {
+        for (index, &day) in days.iter().enumerate() {
+            print_header(day, index > 0);
+
+            let results = child_commands::run_solution(
+                PuzzleId::new(year, day),
+                is_timed,
+                is_release,
+                limits,
+                input,
+                &mut io::stdout(),
+                &mut io::stderr(),
+            )
+            .unwrap_or_else(|e| {
+                eprintln!("failed to run solution: {e}");
+                vec![]
+            });
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            timings.extend(report_results(&results, day));
+        }
+    } else {
+        run_parallel(
+            year,
+            &days,
+            is_release,
+            jobs,
+            limits,
+            input,
+            |index, day, run| {
+                print_header(day, index > 0);
+                let _ = io::stdout().write_all(&run.stdout);
+                let _ = io::stderr().write_all(&run.stderr);
+                timings.extend(report_results(&run.results, day));
+            },
+        );
+    }
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
-            }
-        });
+    print_failures(&timings);
 
     if is_timed {
         let timings = Timings { data: timings };
@@ -46,12 +112,181 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
     }
 }
 
+/// Runs the days of `timings` once more in a build with the `dhat-heap` feature and records the heap usage of their parts.
+/// The output of the solutions is hidden, their errors are forwarded if no heap usage was recorded.
+pub fn measure_memory(year: Year, timings: &mut Timings, limits: Limits) {
+    println!("\n{ANSI_BOLD}Memory{ANSI_RESET}");
+    println!("------");
+
+    for timing in &mut timings.data {
+        let mut stdout = vec![];
+        let mut stderr = vec![];
+        let results = child_commands::run_memory(
+            PuzzleId::new(year, timing.day),
+            limits,
+            &mut stdout,
+            &mut stderr,
+        )
+        .unwrap();
+
+        let mut is_measured = false;
+
+        for result in results {
+            let Some(memory) = result.memory else {
+                continue;
+            };
+
+            match result.part {
+                1 => timing.part_1_memory = Some(memory),
+                2 => timing.part_2_memory = Some(memory),
+                _ => continue,
+            }
+
+            println!("Day {} Part {}: {memory}", timing.day, result.part);
+            is_measured = true;
+        }
+
+        if !is_measured {
+            eprintln!("Day {}: no heap usage recorded.", timing.day);
+            let _ = io::stderr().write_all(&stderr);
+        }
+    }
+}
+
+pub(crate) fn print_header(day: Day, need_space: bool) {
+    if need_space {
+        println!();
+    }
+
+    println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
+    println!("------");
+}
+
+/// Lists the parts that timed out or returned an error.
+fn print_failures(timings: &[Timing]) {
+    let timed_out: Vec<String> = timings
+        .iter()
+        .filter_map(|t| Some(format!("Day {} (part {})", t.day, t.timed_out?)))
+        .collect();
+
+    let failed: Vec<String> = timings
+        .iter()
+        .flat_map(|t| {
+            t.failed
+                .iter()
+                .map(move |part| format!("Day {} (part {part})", t.day))
+        })
+        .collect();
+
+    for (label, parts) in [("Timed out", timed_out), ("Failed", failed)] {
+        if !parts.is_empty() {
+            println!("\n{ANSI_BOLD}{label}:{ANSI_RESET} {}", parts.join(", "));
+        }
+    }
+}
+
+fn report_results(results: &[PartResult], day: Day) -> Option<Timing> {
+    if results.is_empty() {
+        println!("Not solved.");
+        None
+    } else {
+        Some(child_commands::parse_results(results, day))
+    }
+}
+
+/// Output of a solution that ran in the background.
+struct BufferedRun {
+    results: Vec<PartResult>,
+    stdout: Vec<u8>,
+    stderr: Vec<u8>,
+}
+
+/// Runs days on `jobs` worker threads and calls `on_done` for every day, in the order of `days`.
+fn run_parallel(
+    year: Year,
+    days: &[Day],
+    is_release: bool,
+    jobs: usize,
+    limits: Limits,
+    input: Option<&str>,
+    mut on_done: impl FnMut(usize, Day, BufferedRun),
+) {
+    let queue = Mutex::new(days.iter().copied().enumerate());
+    let (tx, rx) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let tx = tx.clone();
+            let queue = &queue;
+
+            scope.spawn(move || loop {
+                let Some((index, day)) = queue.lock().unwrap().next() else {
+                    break;
+                };
+
+                let mut stdout = vec![];
+                let mut stderr = vec![];
+                let results = child_commands::run_solution(
+                    PuzzleId::new(year, day),
+                    false,
+                    is_release,
+                    limits,
+                    input,
+                    &mut stdout,
+                    &mut stderr,
+                )
+                .unwrap_or_else(|e| {
+                    // printed with the rest of the output of the day.
+                    let _ = writeln!(stderr, "failed to run solution: {e}");
+                    vec![]
+                });
+
+                let run = BufferedRun {
+                    results,
+                    stdout,
+                    stderr,
+                };
+
+                if tx.send((index, day, run)).is_err() {
+                    break;
+                }
+            });
+        }
+
+        drop(tx);
+
+        // days can finish out of order, hold them back until all previous days were printed.
+        let mut pending = BTreeMap::new();
+        let mut next_index = 0;
+
+        for (index, day, run) in rx {
+            pending.insert(index, (day, run));
+
+            while let Some((day, run)) = pending.remove(&next_index) {
+                on_done(next_index, day, run);
+                next_index += 1;
+            }
+        }
+    });
+}
+
 #[derive(Debug)]
 pub enum Error {
     BrokenPipe,
+    Protocol(String),
     IO(io::Error),
 }
 
+impl Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::BrokenPipe => write!(f, "could not read the output of the solution."),
+            Error::Protocol(e) => write!(f, "could not read the results of the solution: {e}"),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
 impl From<std::io::Error> for Error {
     fn from(e: std::io::Error) -> Self {
         Error::IO(e)
@@ -59,47 +294,157 @@ impl From<std::io::Error> for Error {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(puzzle: PuzzleId) -> String {
+    format!("./{}", puzzle.bin_path())
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
+/// All solutions live in isolated binaries, which the aggregate `solutions` binary bundles.
+/// This module encapsulates interaction with these binaries, both invoking them as well as reading their results.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{
+        protocol::{self, PartResult, RESULT_FILE_ENV},
+        Day, Limits, PuzzleId, Year,
+    };
     use std::{
-        io::{BufRead, BufReader},
+        env, fs,
+        io::{BufRead, BufReader, Write},
         path::Path,
-        process::{Command, Stdio},
+        process::{self, Command, Stdio},
         thread,
     };
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    /// Run the solution bin for a given puzzle and collect the results it reports.
+    /// The output of the solution is forwarded line by line to `out` and `err`.
+    /// `input` is passed on as `--input`, stdin is inherited so that `-` works.
+    pub fn run_solution(
+        puzzle: PuzzleId,
+        is_timed: bool,
+        is_release: bool,
+        limits: Limits,
+        input: Option<&str>,
+        out: &mut impl Write,
+        err: &mut (impl Write + Send),
+    ) -> Result<Vec<PartResult>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
+        if !Path::new(&get_path_for_bin(puzzle)).exists() {
             return Ok(vec![]);
         }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut bin_args = vec![];
 
-        if is_release {
-            args.push("--release");
+        if is_timed {
+            // mirror `--time` flag to child invocations.
+            bin_args.push("--time".to_string());
         }
 
+        if let Some(input) = input {
+            bin_args.push("--input".to_string());
+            bin_args.push(input.to_string());
+        }
+
+        run_bin(
+            &puzzle.to_string(),
+            profile_args(is_release),
+            &bin_args,
+            limits,
+            out,
+            err,
+        )
+    }
+
+    /// Run the solutions of several days of a year in the aggregate `solutions` binary and collect the results.
+    /// The output of the solutions is forwarded line by line to `out` and `err`.
+    pub fn run_registry(
+        year: Year,
+        days: &[Day],
+        is_timed: bool,
+        is_release: bool,
+        out: &mut impl Write,
+        err: &mut (impl Write + Send),
+    ) -> Result<Vec<PartResult>, Error> {
+        let days: Vec<String> = days.iter().map(ToString::to_string).collect();
+        let mut bin_args = vec![
+            "--year".to_string(),
+            year.to_string(),
+            "--days".to_string(),
+            days.join(","),
+        ];
+
         if is_timed {
-            // mirror `--time` flag to child invocations.
+            bin_args.push("--time".to_string());
+        }
+
+        // a day stopped by a limit would exit the aggregate binary, so it never runs with limits.
+        run_bin(
+            "solutions",
+            profile_args(is_release),
+            &bin_args,
+            Limits::default(),
+            out,
+            err,
+        )
+    }
+
+    /// Run the solution bin for a given puzzle once with the `dhat-heap` feature, which records heap usage.
+    pub fn run_memory(
+        puzzle: PuzzleId,
+        limits: Limits,
+        out: &mut impl Write,
+        err: &mut (impl Write + Send),
+    ) -> Result<Vec<PartResult>, Error> {
+        if !Path::new(&get_path_for_bin(puzzle)).exists() {
+            return Ok(vec![]);
+        }
+
+        run_bin(
+            &puzzle.to_string(),
+            &["--profile", "dhat", "--features", "dhat-heap"],
+            &[],
+            limits,
+            out,
+            err,
+        )
+    }
+
+    fn profile_args(is_release: bool) -> &'static [&'static str] {
+        if is_release {
+            &["--release"]
+        } else {
+            &[]
+        }
+    }
+
+    fn run_bin(
+        bin_name: &str,
+        profile_args: &[&str],
+        bin_args: &[String],
+        limits: Limits,
+        out: &mut impl Write,
+        err: &mut (impl Write + Send),
+    ) -> Result<Vec<PartResult>, Error> {
+        let mut args = vec!["run", "--quiet", "--bin", bin_name];
+        args.extend(profile_args);
+
+        if !bin_args.is_empty() {
             args.push("--");
-            args.push("--time");
+            args.extend(bin_args.iter().map(String::as_str));
         }
 
+        let result_path =
+            env::temp_dir().join(format!("aoc-results-{bin_name}-{}.jsonl", process::id()));
+        // a leftover file from an earlier run would be read as this run's results.
+        let _ = fs::remove_file(&result_path);
+
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+        // forward output to stdout/stderr, results are reported through the result file.
 
-        let mut cmd = Command::new("cargo")
+        let mut cmd = Command::new("cargo");
+        limits.apply_env(&mut cmd);
+
+        let mut cmd = cmd
             .args(&args)
+            .env(RESULT_FILE_ENV, &result_path)
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
             .spawn()?;
@@ -107,87 +452,69 @@ pub mod child_commands {
         let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
         let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
 
-        let mut output = vec![];
-
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
+        thread::scope(|scope| -> Result<(), Error> {
+            scope.spawn(|| {
+                stderr.lines().for_each(|line| {
+                    let _ = writeln!(err, "{}", line.unwrap());
+                });
             });
-        });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+            for line in stdout.lines() {
+                writeln!(out, "{}", line?)?;
+            }
+
+            Ok(())
+        })?;
 
-        thread.join().unwrap();
         cmd.wait()?;
 
-        Ok(output)
+        let results = protocol::read(&result_path).map_err(Error::Protocol);
+        let _ = fs::remove_file(&result_path);
+        results
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
+    pub fn parse_results(results: &[PartResult], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
             part_1: None,
             part_2: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_memory: None,
+            part_2_memory: None,
             total_nanos: 0_f64,
+            timed_out: results.iter().find(|r| r.is_timed_out()).map(|r| r.part),
+            failed: results
+                .iter()
+                .filter(|r| r.is_failed())
+                .map(|r| r.part)
+                .collect(),
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        for result in results.iter().filter(|r| r.is_solved()) {
+            let timing_str = format!("{:.1?}", result.stats.median);
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+            match result.part {
+                1 => {
+                    timings.part_1 = Some(timing_str);
+                    timings.part_1_stats = Some(result.stats);
                 }
+                2 => {
+                    timings.part_2 = Some(timing_str);
+                    timings.part_2_stats = Some(result.stats);
+                }
+                _ => continue,
+            }
 
-                timings.total_nanos += nanos;
-            });
+            #[allow(clippy::cast_precision_loss)]
+            {
+                timings.total_nanos += result.stats.median.as_nanos() as f64;
+            }
+        }
 
         timings
     }
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
-    }
-
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
-
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
     #[cfg(feature = "test_lib")]
     macro_rules! assert_approx_eq {
@@ -204,53 +531,95 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use std::time::Duration;
+
+        use super::parse_results;
 
-        use crate::day;
+        use crate::{
+            day,
+            template::{protocol::PartResult, stats::Stats, PuzzleId},
+            year,
+        };
+
+        fn get_mock_result(part: u8, answer: Option<&str>, nanos: u64) -> PartResult {
+            PartResult {
+                puzzle: PuzzleId::new(year!(2023), day!(1)),
+                part,
+                answer: answer.map(String::from),
+                error: None,
+                timed_out: false,
+                stats: Stats::from_samples(&[Duration::from_nanos(nanos)]),
+                memory: None,
+            }
+        }
 
         #[test]
         fn parses_execution_times() {
-            let res = parse_exec_time(
+            let res = parse_results(
                 &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
+                    get_mock_result(1, Some("0"), 74),
+                    get_mock_result(2, Some("10"), 74_130_000),
                 ],
                 day!(1),
             );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_approx_eq!(res.total_nanos, 74130074_f64);
+            assert_eq!(res.part_1.unwrap(), "74.0ns");
+            assert_eq!(res.part_2.unwrap(), "74.1ms");
+            assert_eq!(
+                res.part_2_stats.unwrap().median,
+                Duration::from_nanos(74_130_000)
+            );
         }
 
         #[test]
         fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
+            let res = parse_results(
                 &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
+                    get_mock_result(1, Some("@ @ @ ( ) ms (2s @ 5 samples)"), 2_000_000_000),
+                    get_mock_result(2, Some("10s\n(100ms @ 1 samples)"), 100_000_000),
                 ],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap(), "2.0s");
+            assert_eq!(res.part_2.unwrap(), "100.0ms");
         }
 
         #[test]
         fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
+            let res = parse_results(
+                &[get_mock_result(1, None, 10), get_mock_result(2, None, 10)],
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 0_f64);
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
+            assert_eq!(res.timed_out, None);
+        }
+
+        #[test]
+        fn parses_timeouts() {
+            let timed_out = PartResult {
+                timed_out: true,
+                ..get_mock_result(2, None, 0)
+            };
+            let res = parse_results(&[get_mock_result(1, Some("0"), 10), timed_out], day!(1));
+            assert_eq!(res.part_1.unwrap(), "10.0ns");
+            assert_eq!(res.part_2, None);
+            assert_eq!(res.timed_out, Some(2));
+            assert!(res.failed.is_empty());
+        }
+
+        #[test]
+        fn parses_errors() {
+            let failed = PartResult {
+                error: Some("invalid digit found in string".into()),
+                ..get_mock_result(1, None, 10)
+            };
+            let res = parse_results(&[failed, get_mock_result(2, None, 10)], day!(1));
+            assert_eq!(res.part_1, None);
+            assert_eq!(res.failed, vec![1]);
+            assert_eq!(res.timed_out, None);
         }
     }
 }
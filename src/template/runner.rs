@@ -2,93 +2,199 @@
 use std::fmt::Display;
 use std::hint::black_box;
 use std::io::{stdout, Write};
-use std::process::Output;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
+use std::{env, process};
 
+use crate::template::answers;
+use crate::template::limits;
+use crate::template::protocol::{self, PartResult};
+use crate::template::stats::{MemoryStats, RunningStats, Stats};
+use crate::template::submissions;
+use crate::template::visualize;
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{aoc_client, PuzzleId, ANSI_ITALIC, ANSI_RESET};
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+/// Time spent running a solution before samples are recorded.
+const WARMUP_TIME: Duration = Duration::from_millis(200);
+/// Time after which benching stops, provided that `MIN_SAMPLES` were taken.
+const BENCH_TIME: Duration = Duration::from_secs(1);
+const MIN_SAMPLES: u128 = 10;
+const MAX_SAMPLES: u128 = 10000;
+/// Benching stops early once the 95% confidence interval of the mean is within this fraction of the mean.
+const TARGET_CONFIDENCE: f64 = 0.01;
+
+/// Return types of solution parts.
+/// `Option<T>` is `None` for parts that are not solved yet, `Result<T, E>` reports why a part failed.
+pub trait PartOutput {
+    /// The answer of the part, `Ok(None)` if it is not solved yet or the error message if it failed.
+    fn to_answer(&self) -> Result<Option<String>, String>;
+}
+
+impl<T: Display> PartOutput for Option<T> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        Ok(self.as_ref().map(ToString::to_string))
+    }
+}
+
+impl<T: Display, E: Display> PartOutput for Result<T, E> {
+    fn to_answer(&self) -> Result<Option<String>, String> {
+        match self {
+            Ok(answer) => Ok(Some(answer.to_string())),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+}
+
+pub fn run_part<I: Clone, R: PartOutput>(
+    func: impl Fn(I) -> R,
+    input: I,
+    puzzle: PuzzleId,
+    part: u8,
+) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, stats, memory) = run_timed(func, input, puzzle, part, |result| {
+        print_result(&result.to_answer(), &part_str, "");
+    });
+
+    let result = result.to_answer();
+
+    print_result(
+        &result,
+        &part_str,
+        &format_duration(&stats.median, stats.samples),
+    );
+
+    if stats.samples > 1 {
+        println!("{part_str} stats: {}", format_stats(&stats));
+    }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let part_result = PartResult {
+        puzzle,
+        part,
+        answer: result.clone().ok().flatten(),
+        error: result.clone().err(),
+        timed_out: false,
+        stats,
+        memory,
+    };
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+    if let Err(e) = protocol::emit(&part_result) {
+        eprintln!("failed to write result: {e}");
+    }
+
+    if let Ok(Some(result)) = result {
+        match submit_result(&result, puzzle, part) {
+            Some(Ok(outcome)) => {
+                println!("{outcome}");
+                if outcome == aoc_client::SubmissionOutcome::Correct {
+                    if let Err(e) = answers::record(puzzle.year, puzzle.day, part, &result) {
+                        eprintln!("failed to record answer: {e}");
+                    }
+                }
+            }
+            Some(Err(e)) => {
+                eprintln!("failed to submit result: {e}");
+                process::exit(1);
+            }
+            None => {}
+        }
     }
 }
 
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///  2. in release, the function is warmed up and benched (approx. 1 second of execution time or 10 samples,
+///     whatever take longer, stopping early once the mean is known precisely enough.)
+///
+/// Parts are stopped once they exceed the limits passed by the runner, see [`limits`].
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    puzzle: PuzzleId,
+    part: u8,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Stats, Option<MemoryStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, memory) = {
         let input = input.clone();
+        let _watchdog = limits::watch_part(puzzle, part);
 
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        let result = func(input);
+        (result, heap_stats())
     };
     let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let stats = if std::env::args().any(|x| x == "--time") {
+        let _watchdog = limits::watch_bench(puzzle, part);
+        // only the first run is visualized.
+        let _paused = visualize::pause();
+        bench(func, input)
     } else {
-        (base_time, 1)
+        Stats::from_samples(&[base_time])
     };
 
-    (result, run.0, run.1)
+    (result, stats, memory)
+}
+
+/// Heap usage since the profiler was started.
+#[cfg(feature = "dhat-heap")]
+fn heap_stats() -> Option<MemoryStats> {
+    let stats = dhat::HeapStats::get();
+    Some(MemoryStats {
+        total_blocks: stats.total_blocks,
+        total_bytes: stats.total_bytes,
+        max_bytes: stats.max_bytes as u64,
+    })
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+#[cfg(not(feature = "dhat-heap"))]
+fn heap_stats() -> Option<MemoryStats> {
+    None
+}
+
+fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I) -> Stats {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
-
-    let mut timers: Vec<Duration> = vec![];
-
-    for _ in 0..bench_iterations {
+    let run_once = || {
         // need a clone here to make the borrow checker happy.
         let cloned = input.clone();
         let timer = Instant::now();
         black_box(func(black_box(cloned)));
-        timers.push(timer.elapsed());
+        timer.elapsed()
+    };
+
+    let warmup = Instant::now();
+    while warmup.elapsed() < WARMUP_TIME {
+        run_once();
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
-}
+    let mut timers: Vec<Duration> = vec![];
+    let mut running = RunningStats::default();
+    let bench = Instant::now();
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+    while running.count() < MAX_SAMPLES {
+        let elapsed = run_once();
+        timers.push(elapsed);
+        running.push(elapsed);
+
+        if running.count() >= MIN_SAMPLES
+            && (bench.elapsed() >= BENCH_TIME
+                || running.relative_confidence_interval() <= TARGET_CONFIDENCE)
+        {
+            break;
+        }
+    }
+
+    Stats::from_samples(&timers)
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -99,12 +205,26 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
-fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
+fn format_stats(stats: &Stats) -> String {
+    format!(
+        "median {:.1?}, mean {:.1?} ± {:.1?}, min {:.1?}, p95 {:.1?}, max {:.1?}, {} outliers in {} samples",
+        stats.median,
+        stats.mean,
+        stats.stddev,
+        stats.min,
+        stats.p95,
+        stats.max,
+        stats.outliers,
+        stats.samples
+    )
+}
+
+fn print_result(result: &Result<Option<String>, String>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
     match result {
-        Some(result) => {
-            if result.to_string().contains('\n') {
+        Ok(Some(result)) => {
+            if result.contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
@@ -123,7 +243,7 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 }
             }
         }
-        None => {
+        Ok(None) => {
             if is_intermediate_result {
                 print!("{part}: ✖");
             } else {
@@ -131,17 +251,28 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
                 println!("{part}: ✖             ");
             }
         }
+        Err(e) => {
+            let str = format!("{part}: {ANSI_BOLD}error:{ANSI_RESET} {e}{duration_str}");
+            if is_intermediate_result {
+                print!("{str}");
+            } else {
+                print!("\r");
+                println!("{str}");
+            }
+        }
     }
 }
 
 /// Parse the arguments passed to `solve` and try to submit one part of the solution if:
 ///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
+///  2. a session cookie is configured.
+///  3. the submission history does not rule out the answer.
+///  4. the solution ran on the puzzle input, not on an input passed with `--input`.
 fn submit_result<T: Display>(
-    result: T,
-    day: Day,
+    result: &T,
+    puzzle: PuzzleId,
     part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
+) -> Option<Result<aoc_client::SubmissionOutcome, aoc_client::AocClientError>> {
     let args: Vec<String> = env::args().collect();
 
     if !args.contains(&"--submit".into()) {
@@ -153,6 +284,11 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
+    if args.contains(&"--input".into()) {
+        eprintln!("Not submitting answers for an input passed with `--input`.");
+        process::exit(1);
+    }
+
     let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
 
     let Ok(part_submit) = args[part_index].parse::<u8>() else {
@@ -164,11 +300,31 @@ fn submit_result<T: Display>(
         return None;
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+    let answer = result.to_string();
+
+    // refuse answers that are already known to be wrong to avoid lockouts.
+    let history = submissions::Submissions::read_from_file(puzzle.year);
+    if let Err(refusal) = history.check(puzzle.day, part, &answer, submissions::now()) {
+        eprintln!("Not submitting `{answer}`: {refusal}");
         process::exit(1);
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    println!("Submitting result...");
+    let outcome = aoc_client::submit(puzzle, part, &answer);
+
+    if let Ok(outcome) = outcome {
+        let submission = submissions::Submission {
+            day: puzzle.day,
+            part,
+            answer,
+            outcome,
+            timestamp: submissions::now(),
+        };
+
+        if let Err(e) = submissions::record(puzzle.year, submission) {
+            eprintln!("failed to record submission: {e}");
+        }
+    }
+
+    Some(outcome)
 }
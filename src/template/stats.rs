@@ -0,0 +1,222 @@
+/// Summary statistics over benchmark samples.
+use std::{fmt::Display, time::Duration};
+
+/// Samples outside of `[q1 - k * iqr, q3 + k * iqr]` are rejected as outliers (Tukey's fences).
+const OUTLIER_FENCE: f64 = 1.5;
+
+/// Statistics of a benchmarked solution part. Outliers are excluded from every value but `outliers`.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Stats {
+    pub samples: u128,
+    pub outliers: u128,
+    pub median: Duration,
+    pub mean: Duration,
+    pub stddev: Duration,
+    pub min: Duration,
+    pub max: Duration,
+    pub p95: Duration,
+}
+
+impl Stats {
+    /// Computes statistics from raw samples, rejecting outliers if there are enough samples to do so.
+    pub fn from_samples(samples: &[Duration]) -> Self {
+        if samples.is_empty() {
+            return Self::default();
+        }
+
+        let mut sorted: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        sorted.sort_unstable_by(f64::total_cmp);
+
+        let retained: Vec<f64> = if sorted.len() >= 4 {
+            let q1 = percentile(&sorted, 0.25);
+            let q3 = percentile(&sorted, 0.75);
+            let iqr = q3 - q1;
+            let (lower, upper) = (q1 - OUTLIER_FENCE * iqr, q3 + OUTLIER_FENCE * iqr);
+            sorted
+                .iter()
+                .copied()
+                .filter(|x| (lower..=upper).contains(x))
+                .collect()
+        } else {
+            sorted.clone()
+        };
+
+        let n = retained.len() as f64;
+        let mean = retained.iter().sum::<f64>() / n;
+        let variance = if retained.len() > 1 {
+            retained.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+
+        Self {
+            samples: samples.len() as u128,
+            outliers: (sorted.len() - retained.len()) as u128,
+            median: to_duration(percentile(&retained, 0.5)),
+            mean: to_duration(mean),
+            stddev: to_duration(variance.sqrt()),
+            min: to_duration(retained[0]),
+            max: to_duration(retained[retained.len() - 1]),
+            p95: to_duration(percentile(&retained, 0.95)),
+        }
+    }
+}
+
+/// Heap usage of a solution part, as measured by dhat.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MemoryStats {
+    /// Number of allocations.
+    pub total_blocks: u64,
+    /// Sum of all allocated bytes.
+    pub total_bytes: u64,
+    /// Largest size of the heap at any time.
+    pub max_bytes: u64,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} peak, {} in {} allocations",
+            format_bytes(self.max_bytes),
+            format_bytes(self.total_bytes),
+            self.total_blocks
+        )
+    }
+}
+
+/// Formats a number of bytes with binary units, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    let units = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < units.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", units[unit])
+}
+
+/// Tracks mean and variance of a stream of samples (Welford's algorithm).
+/// Used to decide when a benchmark has converged without re-computing statistics on every sample.
+#[derive(Debug, Default)]
+pub struct RunningStats {
+    count: u128,
+    mean: f64,
+    m2: f64,
+}
+
+impl RunningStats {
+    pub fn push(&mut self, sample: Duration) {
+        let x = sample.as_nanos() as f64;
+        self.count += 1;
+        let delta = x - self.mean;
+        self.mean += delta / self.count as f64;
+        self.m2 += delta * (x - self.mean);
+    }
+
+    pub fn count(&self) -> u128 {
+        self.count
+    }
+
+    /// Half-width of the 95% confidence interval of the mean, relative to the mean.
+    pub fn relative_confidence_interval(&self) -> f64 {
+        if self.count < 2 || self.mean == 0.0 {
+            return f64::INFINITY;
+        }
+        let n = self.count as f64;
+        let stddev = (self.m2 / (n - 1.0)).sqrt();
+        1.96 * stddev / n.sqrt() / self.mean
+    }
+}
+
+/// Linearly interpolated percentile of sorted values.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p * (sorted.len() - 1) as f64;
+    let (lower, upper) = (rank.floor() as usize, rank.ceil() as usize);
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn to_duration(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{format_bytes, MemoryStats, RunningStats, Stats};
+
+    fn nanos(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_nanos(*x)).collect()
+    }
+
+    #[test]
+    fn computes_stats() {
+        let stats = Stats::from_samples(&nanos(&[10, 20, 30, 40, 50]));
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.outliers, 0);
+        assert_eq!(stats.median, Duration::from_nanos(30));
+        assert_eq!(stats.mean, Duration::from_nanos(30));
+        assert_eq!(stats.stddev, Duration::from_nanos(16));
+        assert_eq!(stats.min, Duration::from_nanos(10));
+        assert_eq!(stats.max, Duration::from_nanos(50));
+        assert_eq!(stats.p95, Duration::from_nanos(48));
+    }
+
+    #[test]
+    fn rejects_outliers() {
+        let stats = Stats::from_samples(&nanos(&[100, 101, 99, 100, 102, 98, 100, 5000]));
+        assert_eq!(stats.samples, 8);
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.max, Duration::from_nanos(102));
+        assert_eq!(stats.median, Duration::from_nanos(100));
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = Stats::from_samples(&nanos(&[42]));
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.median, Duration::from_nanos(42));
+        assert_eq!(stats.stddev, Duration::ZERO);
+    }
+
+    #[test]
+    fn narrows_confidence_interval() {
+        let mut running = RunningStats::default();
+        assert!(running.relative_confidence_interval().is_infinite());
+
+        for x in nanos(&[100, 110, 90, 100]) {
+            running.push(x);
+        }
+        let wide = running.relative_confidence_interval();
+
+        for x in nanos(&[100, 110, 90, 100].repeat(25)) {
+            running.push(x);
+        }
+        assert_eq!(running.count(), 104);
+        assert!(running.relative_confidence_interval() < wide);
+    }
+
+    #[test]
+    fn formats_memory() {
+        assert_eq!(format_bytes(512), "512 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024 * 1024), "3.0 GiB");
+
+        let memory = MemoryStats {
+            total_blocks: 3,
+            total_bytes: 276,
+            max_bytes: 232,
+        };
+        assert_eq!(memory.to_string(), "232 B peak, 276 B in 3 allocations");
+    }
+}
@@ -0,0 +1,448 @@
+/// Keeps a history of submitted answers and their verdicts,
+/// so that answers that are known to be wrong are not submitted again.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::Error,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{aoc_client::SubmissionOutcome, Day, Year};
+
+/// The website refuses answers for a minute after a wrong answer, and locks out for longer if
+/// answers keep coming in during that time.
+const WRONG_ANSWER_COOLDOWN: Duration = Duration::from_secs(60);
+
+fn get_submissions_path(year: Year) -> String {
+    format!("./data/{year}/submissions.json")
+}
+
+/// Seconds since the unix epoch.
+pub fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Submission {
+    pub day: Day,
+    pub part: u8,
+    pub answer: String,
+    pub outcome: SubmissionOutcome,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+impl Submission {
+    /// How long no other answer should be submitted after this one.
+    fn cooldown(&self) -> Duration {
+        match self.outcome {
+            SubmissionOutcome::RateLimited(wait) => wait,
+            SubmissionOutcome::Incorrect
+            | SubmissionOutcome::TooHigh
+            | SubmissionOutcome::TooLow => WRONG_ANSWER_COOLDOWN,
+            SubmissionOutcome::Correct | SubmissionOutcome::WrongLevel => Duration::ZERO,
+        }
+    }
+}
+
+/// Reasons for not submitting an answer.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Refusal {
+    AlreadySolved(String),
+    AlreadyRejected(SubmissionOutcome),
+    TooHigh(String),
+    TooLow(String),
+    Cooldown(Duration),
+}
+
+impl Display for Refusal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Refusal::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with `{answer}`.")
+            }
+            Refusal::AlreadyRejected(outcome) => {
+                write!(f, "this answer was already submitted: {outcome}")
+            }
+            Refusal::TooHigh(bound) => {
+                write!(f, "`{bound}` was already too high, try a lower answer.")
+            }
+            Refusal::TooLow(bound) => {
+                write!(f, "`{bound}` was already too low, try a higher answer.")
+            }
+            Refusal::Cooldown(wait) => {
+                write!(f, "wait {}s before submitting again.", wait.as_secs())
+            }
+        }
+    }
+}
+
+/// History of submissions of a year.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
+        let json = JsonValue::from(self.clone());
+        fs::create_dir_all(format!("./data/{year}"))?;
+        let mut file = fs::File::create(get_submissions_path(year))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate submissions of a year from a JSON file. If not present, returns an empty history.
+    pub fn read_from_file(year: Year) -> Self {
+        let Ok(s) = fs::read_to_string(get_submissions_path(year)) else {
+            return Submissions::default();
+        };
+
+        match Submissions::try_from(s) {
+            Ok(submissions) => submissions,
+            Err(e) => {
+                eprintln!("{e}");
+                Submissions::default()
+            }
+        }
+    }
+
+    /// Checks whether an answer is worth submitting at time `now` (seconds since the unix epoch).
+    pub fn check(&self, day: Day, part: u8, answer: &str, now: u64) -> Result<(), Refusal> {
+        let history: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|s| s.day == day && s.part == part)
+            .collect();
+
+        if let Some(solved) = history
+            .iter()
+            .find(|s| s.outcome == SubmissionOutcome::Correct)
+        {
+            return Err(Refusal::AlreadySolved(solved.answer.clone()));
+        }
+
+        if let Some(rejected) = history.iter().find(|s| {
+            s.answer == answer
+                && matches!(
+                    s.outcome,
+                    SubmissionOutcome::Incorrect
+                        | SubmissionOutcome::TooHigh
+                        | SubmissionOutcome::TooLow
+                )
+        }) {
+            return Err(Refusal::AlreadyRejected(rejected.outcome));
+        }
+
+        if let Ok(value) = answer.parse::<i128>() {
+            let bound = |outcome: SubmissionOutcome| {
+                history
+                    .iter()
+                    .filter(move |s| s.outcome == outcome)
+                    .filter_map(|s| Some((s.answer.parse::<i128>().ok()?, &s.answer)))
+            };
+
+            if let Some((_, high)) = bound(SubmissionOutcome::TooHigh)
+                .filter(|(bound, _)| value >= *bound)
+                .min()
+            {
+                return Err(Refusal::TooHigh(high.clone()));
+            }
+
+            if let Some((_, low)) = bound(SubmissionOutcome::TooLow)
+                .filter(|(bound, _)| value <= *bound)
+                .max()
+            {
+                return Err(Refusal::TooLow(low.clone()));
+            }
+        }
+
+        // the cooldown applies to all parts of a year.
+        let remaining = self
+            .data
+            .iter()
+            .filter_map(|s| (s.timestamp + s.cooldown().as_secs()).checked_sub(now))
+            .filter(|remaining| *remaining > 0)
+            .max();
+
+        match remaining {
+            Some(remaining) => Err(Refusal::Cooldown(Duration::from_secs(remaining))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Records a submission in the history of its year.
+pub fn record(year: Year, submission: Submission) -> Result<(), Error> {
+    let mut submissions = Submissions::read_from_file(year);
+    submissions.data.push(submission);
+    submissions.store_file(year)
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn outcome_to_str(outcome: SubmissionOutcome) -> &'static str {
+    match outcome {
+        SubmissionOutcome::Correct => "correct",
+        SubmissionOutcome::Incorrect => "incorrect",
+        SubmissionOutcome::TooHigh => "too_high",
+        SubmissionOutcome::TooLow => "too_low",
+        SubmissionOutcome::RateLimited(_) => "rate_limited",
+        SubmissionOutcome::WrongLevel => "wrong_level",
+    }
+}
+
+fn outcome_from_str(s: &str, wait: Duration) -> Option<SubmissionOutcome> {
+    match s {
+        "correct" => Some(SubmissionOutcome::Correct),
+        "incorrect" => Some(SubmissionOutcome::Incorrect),
+        "too_high" => Some(SubmissionOutcome::TooHigh),
+        "too_low" => Some(SubmissionOutcome::TooLow),
+        "rate_limited" => Some(SubmissionOutcome::RateLimited(wait)),
+        "wrong_level" => Some(SubmissionOutcome::WrongLevel),
+        _ => None,
+    }
+}
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        map.insert("part".into(), JsonValue::Number(value.part.into()));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "outcome".into(),
+            JsonValue::String(outcome_to_str(value.outcome).into()),
+        );
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "timestamp".into(),
+                JsonValue::Number(value.timestamp as f64),
+            );
+
+            if let SubmissionOutcome::RateLimited(wait) = value.outcome {
+                map.insert("wait_secs".into(), JsonValue::Number(wait.as_secs() as f64));
+            }
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or("Expected submission.day to be a Day struct.")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u8)
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let wait = json
+            .get("wait_secs")
+            .and_then(|v| v.get::<f64>())
+            .map_or(Duration::ZERO, |x| Duration::from_secs(*x as u64));
+
+        let outcome = json
+            .get("outcome")
+            .and_then(|v| v.get::<String>())
+            .and_then(|s| outcome_from_str(s, wait))
+            .ok_or("Expected submission.outcome to be a known verdict.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .map(|x| *x as u64)
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        Ok(Submission {
+            day,
+            part,
+            answer: answer.clone(),
+            outcome,
+            timestamp,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Refusal, Submission, Submissions};
+    use crate::{day, template::aoc_client::SubmissionOutcome};
+
+    fn submission(part: u8, answer: &str, outcome: SubmissionOutcome) -> Submission {
+        Submission {
+            day: day!(1),
+            part,
+            answer: answer.into(),
+            outcome,
+            timestamp: 1000,
+        }
+    }
+
+    fn get_mock_submissions() -> Submissions {
+        Submissions {
+            data: vec![
+                submission(1, "500", SubmissionOutcome::TooHigh),
+                submission(1, "100", SubmissionOutcome::TooLow),
+                submission(1, "abc", SubmissionOutcome::Incorrect),
+                submission(2, "1", SubmissionOutcome::Correct),
+            ],
+        }
+    }
+
+    #[test]
+    fn allows_new_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(submissions.check(day!(1), 1, "300", 2000), Ok(()));
+        assert_eq!(submissions.check(day!(2), 1, "500", 2000), Ok(()));
+    }
+
+    #[test]
+    fn refuses_rejected_answers() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "abc", 2000),
+            Err(Refusal::AlreadyRejected(SubmissionOutcome::Incorrect))
+        );
+    }
+
+    #[test]
+    fn refuses_answers_outside_bounds() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 1, "501", 2000),
+            Err(Refusal::TooHigh("500".into()))
+        );
+        assert_eq!(
+            submissions.check(day!(1), 1, "99", 2000),
+            Err(Refusal::TooLow("100".into()))
+        );
+    }
+
+    #[test]
+    fn refuses_solved_parts() {
+        let submissions = get_mock_submissions();
+        assert_eq!(
+            submissions.check(day!(1), 2, "2", 2000),
+            Err(Refusal::AlreadySolved("1".into()))
+        );
+    }
+
+    #[test]
+    fn reports_remaining_cooldown() {
+        let mut submissions = get_mock_submissions();
+        submissions.data.push(submission(
+            1,
+            "300",
+            SubmissionOutcome::RateLimited(Duration::from_secs(60)),
+        ));
+        assert_eq!(
+            submissions.check(day!(1), 1, "300", 1045),
+            Err(Refusal::Cooldown(Duration::from_secs(15)))
+        );
+        assert_eq!(submissions.check(day!(1), 1, "300", 1060), Ok(()));
+    }
+
+    #[test]
+    fn waits_after_wrong_answers() {
+        let mut submissions = get_mock_submissions();
+        submissions.data.push(Submission {
+            timestamp: 1990,
+            ..submission(1, "200", SubmissionOutcome::TooLow)
+        });
+        assert_eq!(
+            submissions.check(day!(1), 1, "300", 2000),
+            Err(Refusal::Cooldown(Duration::from_secs(50)))
+        );
+        assert_eq!(
+            submissions.check(day!(2), 1, "300", 2049),
+            Err(Refusal::Cooldown(Duration::from_secs(1)))
+        );
+        assert_eq!(submissions.check(day!(1), 1, "300", 2050), Ok(()));
+    }
+
+    #[test]
+    fn round_trips_json() {
+        let mut submissions = get_mock_submissions();
+        submissions.data.push(submission(
+            1,
+            "300",
+            SubmissionOutcome::RateLimited(Duration::from_secs(60)),
+        ));
+        let json = tinyjson::JsonValue::from(submissions.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed.data, submissions.data);
+    }
+}
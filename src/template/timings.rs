@@ -1,9 +1,14 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::Error, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    stats::{MemoryStats, Stats},
+    Day, Year,
+};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+fn get_timings_path(year: Year) -> String {
+    format!("./data/{year}/timings.json")
+}
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
@@ -11,7 +16,36 @@ pub struct Timing {
     pub day: Day,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub part_1_stats: Option<Stats>,
+    pub part_2_stats: Option<Stats>,
+    /// Heap usage of the parts, recorded by `cargo time --memory`.
+    pub part_1_memory: Option<MemoryStats>,
+    pub part_2_memory: Option<MemoryStats>,
     pub total_nanos: f64,
+    /// The part that was stopped by a timeout, if any.
+    pub timed_out: Option<u8>,
+    /// Parts that returned an error.
+    pub failed: Vec<u8>,
+}
+
+impl Timing {
+    /// Benchmark statistics of a part, if it was benchmarked.
+    pub fn part_stats(&self, part: u8) -> Option<&Stats> {
+        match part {
+            1 => self.part_1_stats.as_ref(),
+            2 => self.part_2_stats.as_ref(),
+            _ => None,
+        }
+    }
+
+    /// Heap usage of a part, if it was measured.
+    pub fn part_memory(&self, part: u8) -> Option<&MemoryStats> {
+        match part {
+            1 => self.part_1_memory.as_ref(),
+            2 => self.part_2_memory.as_ref(),
+            _ => None,
+        }
+    }
 }
 
 /// Represents benchmark times for a set of days.
@@ -22,16 +56,17 @@ pub struct Timings {
 }
 
 impl Timings {
-    /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    /// Dehydrate timings of a year to a JSON file.
+    pub fn store_file(&self, year: Year) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        fs::create_dir_all(format!("./data/{year}"))?;
+        let mut file = fs::File::create(get_timings_path(year))?;
         json.format_to(&mut file)
     }
 
-    /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
+    /// Rehydrate timings of a year from a JSON file. If not present, returns empty timings.
+    pub fn read_from_file(year: Year) -> Self {
+        let s = fs::read_to_string(get_timings_path(year))
             .map_err(|x| x.to_string())
             .and_then(Timings::try_from);
 
@@ -49,7 +84,15 @@ impl Timings {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+
+            // keep heap usage of earlier runs that did not measure it.
+            if let Some(old) = self.data.iter().find(|t| t.day == timing.day) {
+                timing.part_1_memory = timing.part_1_memory.or(old.part_1_memory);
+                timing.part_2_memory = timing.part_2_memory.or(old.part_2_memory);
+            }
+
+            data.push(timing);
         }
 
         for timing in &self.data {
@@ -140,6 +183,50 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "part_1_stats".into(),
+            value
+                .part_1_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value
+                .part_2_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        for (key, memory) in [
+            ("part_1_memory", &value.part_1_memory),
+            ("part_2_memory", &value.part_2_memory),
+        ] {
+            map.insert(
+                key.into(),
+                memory.as_ref().map_or(JsonValue::Null, JsonValue::from),
+            );
+        }
+
+        map.insert(
+            "timed_out".into(),
+            value
+                .timed_out
+                .map_or(JsonValue::Null, |part| JsonValue::Number(part.into())),
+        );
+
+        map.insert(
+            "failed".into(),
+            JsonValue::Array(
+                value
+                    .failed
+                    .iter()
+                    .map(|part| JsonValue::Number((*part).into()))
+                    .collect(),
+            ),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -173,11 +260,162 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // stats are optional to stay compatible with timings stored before they were recorded.
+        let part_1_stats = match json.get("part_1_stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        let part_2_stats = match json.get("part_2_stats") {
+            Some(v) if !v.is_null() => Some(Stats::try_from(v)?),
+            _ => None,
+        };
+
+        let part_1_memory = match json.get("part_1_memory") {
+            Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+            _ => None,
+        };
+
+        let part_2_memory = match json.get("part_2_memory") {
+            Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+            _ => None,
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let timed_out = json
+            .get("timed_out")
+            .and_then(|v| v.get::<f64>())
+            .map(|part| *part as u8);
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let failed = json
+            .get("failed")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .map(|parts| {
+                parts
+                    .iter()
+                    .filter_map(|part| part.get::<f64>().map(|part| *part as u8))
+                    .collect()
+            })
+            .unwrap_or_default();
+
         Ok(Timing {
             day,
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_stats,
+            part_2_stats,
+            part_1_memory,
+            part_2_memory,
             total_nanos,
+            timed_out,
+            failed,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[allow(clippy::cast_precision_loss)]
+fn nanos_to_json(duration: Duration) -> JsonValue {
+    JsonValue::Number(duration.as_nanos() as f64)
+}
+
+impl From<&Stats> for JsonValue {
+    fn from(value: &Stats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+            map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+        }
+        map.insert("median_nanos".into(), nanos_to_json(value.median));
+        map.insert("mean_nanos".into(), nanos_to_json(value.mean));
+        map.insert("stddev_nanos".into(), nanos_to_json(value.stddev));
+        map.insert("min_nanos".into(), nanos_to_json(value.min));
+        map.insert("max_nanos".into(), nanos_to_json(value.max));
+        map.insert("p95_nanos".into(), nanos_to_json(value.p95));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Stats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected timing stats.{key} to be a number."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let duration = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Ok(Stats {
+            samples: number("samples")? as u128,
+            outliers: number("outliers")? as u128,
+            median: duration("median_nanos")?,
+            mean: duration("mean_nanos")?,
+            stddev: duration("stddev_nanos")?,
+            min: duration("min_nanos")?,
+            max: duration("max_nanos")?,
+            p95: duration("p95_nanos")?,
+        })
+    }
+}
+
+impl From<&MemoryStats> for JsonValue {
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        #[allow(clippy::cast_precision_loss)]
+        {
+            map.insert(
+                "total_blocks".into(),
+                JsonValue::Number(value.total_blocks as f64),
+            );
+            map.insert(
+                "total_bytes".into(),
+                JsonValue::Number(value.total_bytes as f64),
+            );
+            map.insert(
+                "max_bytes".into(),
+                JsonValue::Number(value.max_bytes as f64),
+            );
+        }
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected memory stats to be a JSON object.")?;
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|x| x as u64)
+                .ok_or(format!("Expected memory stats.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            total_blocks: number("total_blocks")?,
+            total_bytes: number("total_bytes")?,
+            max_bytes: number("max_bytes")?,
         })
     }
 }
@@ -197,25 +435,45 @@ mod tests {
                     day: day!(1),
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 3e+10,
+                    timed_out: None,
+                    failed: vec![],
                 },
                 Timing {
                     day: day!(2),
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 7e+10,
+                    timed_out: None,
+                    failed: vec![],
                 },
                 Timing {
                     day: day!(4),
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 4e+10,
+                    timed_out: None,
+                    failed: vec![],
                 },
             ],
         }
     }
 
     mod deserialization {
+        use std::time::Duration;
+
         use crate::{day, template::timings::Timings};
 
         #[test]
@@ -228,6 +486,27 @@ mod tests {
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
+            assert_eq!(timing.timed_out, None);
+        }
+
+        #[test]
+        fn handles_json_timeouts() {
+            let json = r#"{ "data": [{ "day": "16", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "timed_out": 2 }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].timed_out, Some(2));
+        }
+
+        #[test]
+        fn handles_json_stats() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1.0ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 100, "outliers": 2, "median_nanos": 1000000, "mean_nanos": 1100000, "stddev_nanos": 50000, "min_nanos": 900000, "max_nanos": 1400000, "p95_nanos": 1300000 }, "part_2_stats": null }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let stats = timing.part_1_stats.unwrap();
+            assert_eq!(stats.samples, 100);
+            assert_eq!(stats.outliers, 2);
+            assert_eq!(stats.median, Duration::from_millis(1));
+            assert_eq!(stats.p95, Duration::from_micros(1300));
+            assert_eq!(timing.part_2_stats, None);
         }
 
         #[test]
@@ -288,11 +567,17 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 3_000_000_000_f64,
+                    timed_out: None,
+                    failed: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -302,11 +587,17 @@ mod tests {
                     day: day!(1),
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 1_000_000_000_f64,
+                    timed_out: None,
+                    failed: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -316,17 +607,24 @@ mod tests {
                     day: day!(1),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 0.0,
+                    timed_out: None,
+                    failed: vec![],
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
     mod merge {
         use crate::{
             day,
+            template::stats::MemoryStats,
             template::timings::{Timing, Timings},
         };
 
@@ -340,7 +638,13 @@ mod tests {
                     day: day!(3),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 0_f64,
+                    timed_out: None,
+                    failed: vec![],
                 }],
             };
             let merged = timings.merge(&other);
@@ -360,7 +664,13 @@ mod tests {
                     day: day!(2),
                     part_1: None,
                     part_2: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
                     total_nanos: 0_f64,
+                    timed_out: None,
+                    failed: vec![],
                 }],
             };
             let merged = timings.merge(&other);
@@ -387,5 +697,20 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_memory_of_stored_timings() {
+            let mut timings = get_mock_timings();
+            let memory = MemoryStats {
+                total_blocks: 1,
+                total_bytes: 2,
+                max_bytes: 2,
+            };
+            timings.data[0].part_1_memory = Some(memory);
+
+            let merged = timings.merge(&get_mock_timings());
+            assert_eq!(merged.data[0].part_1_memory, Some(memory));
+            assert_eq!(merged.data[0].part_2_memory, None);
+        }
     }
 }
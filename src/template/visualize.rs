@@ -0,0 +1,418 @@
+/// Visualizations of the state of solutions, e.g. a grid that changes while a puzzle is solved.
+///
+/// Solutions emit frames with [`frame`]. `cargo solve <day> --visualize` renders them as an ANSI animation,
+/// `--visualize-file <path>` writes them to a file instead. Without these flags, frames are not even constructed,
+/// so emitting them does not slow down solutions or benchmarks.
+use std::{
+    env,
+    fmt::Write as _,
+    fs::File,
+    io::{self, BufWriter, Write},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex, OnceLock,
+    },
+    thread,
+    time::Duration,
+};
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+/// Frames per second of animations, unless set with `--fps`.
+const DEFAULT_FPS: f64 = 10.0;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Color {
+    Red,
+    Green,
+    Yellow,
+    Blue,
+    Magenta,
+    Cyan,
+    White,
+    Gray,
+}
+
+impl Color {
+    fn ansi_code(self) -> u8 {
+        match self {
+            Color::Red => 31,
+            Color::Green => 32,
+            Color::Yellow => 33,
+            Color::Blue => 34,
+            Color::Magenta => 35,
+            Color::Cyan => 36,
+            Color::White => 37,
+            Color::Gray => 90,
+        }
+    }
+}
+
+/// A cell of a frame. Highlighted cells are shown in reverse video.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Cell {
+    pub symbol: char,
+    pub color: Option<Color>,
+    pub highlight: bool,
+}
+
+impl Cell {
+    pub fn new(symbol: char) -> Self {
+        Cell {
+            symbol,
+            color: None,
+            highlight: false,
+        }
+    }
+
+    #[must_use]
+    pub fn color(self, color: Color) -> Self {
+        Cell {
+            color: Some(color),
+            ..self
+        }
+    }
+
+    #[must_use]
+    pub fn highlight(self) -> Self {
+        Cell {
+            highlight: true,
+            ..self
+        }
+    }
+
+    fn style(&self) -> (Option<Color>, bool) {
+        (self.color, self.highlight)
+    }
+}
+
+impl From<char> for Cell {
+    fn from(symbol: char) -> Self {
+        Cell::new(symbol)
+    }
+}
+
+/// A grid of cells with an optional caption, e.g. the current step of a simulation.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Frame {
+    width: usize,
+    height: usize,
+    cells: Vec<Cell>,
+    caption: Option<String>,
+}
+
+impl Frame {
+    /// Creates an empty frame of `width` columns and `height` rows.
+    pub fn new(width: usize, height: usize) -> Self {
+        Frame::from_fn(width, height, |_, _| Cell::new(' '))
+    }
+
+    /// Creates a frame with the cell of every column `x` and row `y`.
+    pub fn from_fn<C: Into<Cell>>(
+        width: usize,
+        height: usize,
+        mut cell: impl FnMut(usize, usize) -> C,
+    ) -> Self {
+        let cells = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| cell(x, y).into())
+            .collect();
+
+        Frame {
+            width,
+            height,
+            cells,
+            caption: None,
+        }
+    }
+
+    /// Creates a frame from lines of text, e.g. the output of a `Display` impl. Short lines are padded.
+    pub fn from_lines(s: &str) -> Self {
+        let lines: Vec<Vec<char>> = s.lines().map(|line| line.chars().collect()).collect();
+        let width = lines.iter().map(Vec::len).max().unwrap_or(0);
+
+        Frame::from_fn(width, lines.len(), |x, y| {
+            lines[y].get(x).copied().unwrap_or(' ')
+        })
+    }
+
+    #[must_use]
+    pub fn with_caption(self, caption: impl Into<String>) -> Self {
+        Frame {
+            caption: Some(caption.into()),
+            ..self
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&Cell> {
+        (x < self.width && y < self.height).then(|| &self.cells[y * self.width + x])
+    }
+
+    /// Replaces a cell. Cells outside of the frame are ignored.
+    pub fn set(&mut self, x: usize, y: usize, cell: impl Into<Cell>) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = cell.into();
+        }
+    }
+
+    /// Changes a cell in place, e.g. to highlight it. Cells outside of the frame are ignored.
+    pub fn update(&mut self, x: usize, y: usize, f: impl FnOnce(Cell) -> Cell) {
+        if let Some(cell) = self.get(x, y).copied() {
+            self.set(x, y, f(cell));
+        }
+    }
+
+    /// Renders the frame as text, with colors and highlights if `ansi` is set.
+    pub fn render(&self, ansi: bool) -> String {
+        let mut s = String::with_capacity(self.cells.len() * 2);
+
+        if let Some(caption) = &self.caption {
+            if ansi {
+                let _ = writeln!(s, "{ANSI_BOLD}{caption}{ANSI_RESET}");
+            } else {
+                let _ = writeln!(s, "{caption}");
+            }
+        }
+
+        for row in self.cells.chunks(self.width.max(1)) {
+            // escape codes are only written where the style changes.
+            let mut style = (None, false);
+
+            for cell in row {
+                if ansi && cell.style() != style {
+                    if style != (None, false) {
+                        s.push_str(ANSI_RESET);
+                    }
+                    if let Some(color) = cell.color {
+                        let _ = write!(s, "\x1b[{}m", color.ansi_code());
+                    }
+                    if cell.highlight {
+                        s.push_str("\x1b[7m");
+                    }
+                    style = cell.style();
+                }
+                s.push(cell.symbol);
+            }
+
+            if style != (None, false) {
+                s.push_str(ANSI_RESET);
+            }
+            s.push('\n');
+        }
+
+        s
+    }
+}
+
+/// How frames are shown. Parsed from the `--visualize`, `--fps` and `--visualize-file` flags.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Settings {
+    /// Frames per second of the animation.
+    pub fps: f64,
+    /// File to write the frames to instead of animating them.
+    pub file: Option<String>,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            fps: DEFAULT_FPS,
+            file: None,
+        }
+    }
+}
+
+impl Settings {
+    /// Reads the settings from command-line arguments. Returns `None` if frames should not be shown.
+    pub fn from_args(args: &[String]) -> Option<Self> {
+        let value = |flag: &str| {
+            let index = args.iter().position(|x| x == flag)?;
+            args.get(index + 1)
+        };
+
+        let file = value("--visualize-file").cloned();
+
+        if file.is_none() && !args.iter().any(|x| x == "--visualize") {
+            return None;
+        }
+
+        let fps = value("--fps")
+            .and_then(|fps| fps.parse::<f64>().ok())
+            .filter(|fps| fps.is_finite() && *fps > 0.0)
+            .unwrap_or(DEFAULT_FPS);
+
+        Some(Settings { fps, file })
+    }
+
+    /// Arguments that pass the settings to a solution binary.
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = vec![
+            "--visualize".to_string(),
+            "--fps".to_string(),
+            self.fps.to_string(),
+        ];
+
+        if let Some(file) = &self.file {
+            args.push("--visualize-file".to_string());
+            args.push(file.clone());
+        }
+
+        args
+    }
+}
+
+enum Sink {
+    Terminal,
+    File(BufWriter<File>),
+}
+
+struct Output {
+    settings: Settings,
+    sink: Sink,
+    frames: usize,
+}
+
+impl Output {
+    fn emit(&mut self, frame: &Frame) -> Result<(), io::Error> {
+        self.frames += 1;
+
+        match &mut self.sink {
+            Sink::Terminal => {
+                let mut stdout = io::stdout().lock();
+                // clear the screen once, later frames are drawn over the previous one.
+                if self.frames == 1 {
+                    write!(stdout, "\x1b[2J")?;
+                }
+                write!(stdout, "\x1b[H\x1b[J{}", frame.render(true))?;
+                writeln!(stdout, "frame {}", self.frames)?;
+                stdout.flush()?;
+                thread::sleep(Duration::from_secs_f64(1.0 / self.settings.fps));
+                Ok(())
+            }
+            Sink::File(file) => {
+                // keep the colors, e.g. to page through the frames with `less -R`.
+                writeln!(file, "--- frame {} ---", self.frames)?;
+                write!(file, "{}", frame.render(true))?;
+                file.flush()
+            }
+        }
+    }
+}
+
+static PAUSED: AtomicBool = AtomicBool::new(false);
+
+fn output() -> Option<&'static Mutex<Output>> {
+    static OUTPUT: OnceLock<Option<Mutex<Output>>> = OnceLock::new();
+
+    OUTPUT
+        .get_or_init(|| {
+            let settings = Settings::from_args(&env::args().collect::<Vec<_>>())?;
+
+            let sink = match &settings.file {
+                Some(path) => match File::create(path) {
+                    Ok(file) => Sink::File(BufWriter::new(file)),
+                    Err(e) => {
+                        eprintln!("Failed to create visualization file \"{path}\": {e}");
+                        return None;
+                    }
+                },
+                None => Sink::Terminal,
+            };
+
+            Some(Mutex::new(Output {
+                settings,
+                sink,
+                frames: 0,
+            }))
+        })
+        .as_ref()
+}
+
+/// Whether emitted frames are shown. Use this to skip bookkeeping that is only needed for visualizations.
+pub fn is_enabled() -> bool {
+    !PAUSED.load(Ordering::Relaxed) && output().is_some()
+}
+
+/// Emits a frame. `frame` is only called if frames are shown.
+pub fn frame(frame: impl FnOnce() -> Frame) {
+    if !is_enabled() {
+        return;
+    }
+
+    let Some(output) = output() else {
+        return;
+    };
+
+    let frame = frame();
+    if let Err(e) = output.lock().unwrap().emit(&frame) {
+        eprintln!("Failed to show frame: {e}");
+    }
+}
+
+/// Hides frames until dropped.
+pub struct Paused(bool);
+
+impl Drop for Paused {
+    fn drop(&mut self) {
+        PAUSED.store(self.0, Ordering::Relaxed);
+    }
+}
+
+/// Hides frames while the returned guard is alive, e.g. while benchmarking or in a part that repeats
+/// what another part already showed.
+pub fn pause() -> Paused {
+    Paused(PAUSED.swap(true, Ordering::Relaxed))
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Cell, Color, Frame, Settings};
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn renders_frames() {
+        let mut frame = Frame::from_lines("#.\n.").with_caption("step 1");
+        assert_eq!(frame.width(), 2);
+        assert_eq!(frame.height(), 2);
+        assert_eq!(frame.render(false), "step 1\n#.\n. \n");
+
+        frame.set(1, 1, Cell::new('>').color(Color::Red));
+        frame.set(0, 1, Cell::new('<').color(Color::Red));
+        frame.update(0, 0, Cell::highlight);
+        frame.set(5, 5, 'x');
+
+        assert_eq!(frame.render(false), "step 1\n#.\n<>\n");
+        assert_eq!(
+            frame.render(true),
+            "\x1b[1mstep 1\x1b[0m\n\x1b[7m#\x1b[0m.\n\x1b[31m<>\x1b[0m\n"
+        );
+    }
+
+    #[test]
+    fn parses_settings() {
+        assert_eq!(Settings::from_args(&args("--time")), None);
+        assert_eq!(
+            Settings::from_args(&args("--visualize")),
+            Some(Settings::default())
+        );
+
+        let settings = Settings::from_args(&args("--fps 30 --visualize-file out.txt")).unwrap();
+        assert_eq!(settings.fps, 30.0);
+        assert_eq!(settings.file.as_deref(), Some("out.txt"));
+        assert_eq!(Settings::from_args(&settings.to_args()), Some(settings));
+
+        let settings = Settings::from_args(&args("--visualize --fps 0")).unwrap();
+        assert_eq!(settings.fps, 10.0);
+    }
+}
@@ -0,0 +1,224 @@
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, Local};
+
+use crate::template::Day;
+
+/// A valid year of advent (i.e. an integer in range 2015 to 9999).
+///
+/// # Display
+/// This value displays as a four digit number.
+///
+/// ```
+/// # use advent_of_code::template::Year;
+/// let year = Year::new(2023).unwrap();
+/// assert_eq!(year.to_string(), "2023")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's in the valid range,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if !(2015..=9999).contains(&year) {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    /// Reads the year from the module path of a solution in a const context.
+    /// The last path segment is `2023_01` for a solution binary and `_2023_01` for a module of
+    /// the aggregate `solutions` binary.
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __from_module_path(path: &str) -> Self {
+        let bytes = path.as_bytes();
+
+        // find the start of the last path segment, skipping an optional leading underscore.
+        let mut start = bytes.len();
+        while start > 0 && bytes[start - 1] != b':' {
+            start -= 1;
+        }
+        if start < bytes.len() && bytes[start] == b'_' {
+            start += 1;
+        }
+
+        assert!(
+            bytes.len() > start + 4 && bytes[start + 4] == b'_',
+            "solution binaries need to be named like `2023-01`"
+        );
+
+        let mut year = 0;
+        let mut i = 0;
+        while i < 4 {
+            assert!(
+                bytes[start + i].is_ascii_digit(),
+                "solution binaries need to be named like `2023-01`"
+            );
+            year = year * 10 + (bytes[start + i] - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(year >= 2015, "invalid year in binary name");
+        Self(year)
+    }
+
+    /// Reads the default year from the `AOC_YEAR` environment variable.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR").ok()?.parse().ok()
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+#[cfg(feature = "today")]
+impl Year {
+    /// Returns the current year.
+    pub fn today() -> Option<Self> {
+        Self::new(u16::try_from(Local::now().year()).ok()?)
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:04}", self.0)
+    }
+}
+
+impl PartialEq<u16> for Year {
+    fn eq(&self, other: &u16) -> bool {
+        self.0.eq(other)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year number of 2015 or later")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a single puzzle by its year and day.
+///
+/// # Display
+/// This value displays as the name of the solution binary.
+///
+/// ```
+/// # use advent_of_code::{day, year, template::PuzzleId};
+/// let puzzle = PuzzleId::new(year!(2023), day!(1));
+/// assert_eq!(puzzle.to_string(), "2023-01")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Returns the puzzle of the current day if it's between the 1st and the 25th of december.
+    #[cfg(feature = "today")]
+    pub fn today() -> Option<Self> {
+        Some(Self::new(Year::today()?, Day::today()?))
+    }
+
+    /// Path of a data file for this puzzle, e.g. `data/2023/inputs/01.txt`.
+    pub fn data_path(&self, folder: &str, extension: &str) -> String {
+        format!("data/{}/{folder}/{}.{extension}", self.year, self.day)
+    }
+
+    /// Path of the solution binary for this puzzle, e.g. `src/bin/2023-01.rs`.
+    pub fn bin_path(&self) -> String {
+        format!("src/bin/{self}.rs")
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015 && $year <= 9999,
+            concat!(
+                "invalid year number `",
+                $year,
+                "`, expecting a value of 2015 or later"
+            ),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{PuzzleId, Year};
+    use crate::{day, year};
+
+    #[test]
+    fn parses_years() {
+        assert_eq!("2023".parse::<Year>().unwrap(), 2023);
+        assert!("2014".parse::<Year>().is_err());
+        assert!("foo".parse::<Year>().is_err());
+    }
+
+    #[test]
+    fn reads_year_from_module_path() {
+        assert_eq!(Year::__from_module_path("2022_05"), 2022);
+        assert_eq!(Year::__from_module_path("solutions::_2022_05"), 2022);
+    }
+
+    #[test]
+    fn builds_puzzle_paths() {
+        let puzzle = PuzzleId::new(year!(2022), day!(5));
+        assert_eq!(puzzle.to_string(), "2022-05");
+        assert_eq!(puzzle.bin_path(), "src/bin/2022-05.rs");
+        assert_eq!(puzzle.data_path("inputs", "txt"), "data/2022/inputs/05.txt");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
@@ -0,0 +1,34 @@
+//! %YEAR% day %DAY%: %TITLE%
+use advent_of_code::grid::Grid;
+
+advent_of_code::solution!(%DAY_NUMBER%);
+
+pub fn part_one(input: &str) -> Option<%ANSWER_TYPE%> {
+    let _grid = Grid::parse(input, Some).ok()?;
+    None
+}
+
+pub fn part_two(input: &str) -> Option<%ANSWER_TYPE%> {
+    let _grid = Grid::parse(input, Some).ok()?;
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // generates a test for every expected answer in `data/%YEAR%/examples/%DAY%.cases`.
+    advent_of_code::example_tests!();
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None);
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, None);
+    }
+}